@@ -0,0 +1,53 @@
+//! Measures the per-call overhead of the digital bridge movement path against no-op pins, so
+//! regressions in the hot path of tight control loops show up.
+//!
+//! ```sh
+//! cargo bench --bench movement
+//! ```
+
+use std::convert::Infallible;
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use embedded_hal::digital::{ErrorType, OutputPin};
+
+use drv8833_driver::{Breaks, Bridge, Movement};
+
+/// A pin whose writes always succeed and do nothing, leaving only the driver's own overhead.
+struct NoopPin;
+
+impl ErrorType for NoopPin {
+    type Error = Infallible;
+}
+
+impl OutputPin for NoopPin {
+    fn set_low(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn set_high(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+fn movement(c: &mut Criterion) {
+    let mut bridge = Bridge::new(NoopPin, NoopPin);
+
+    c.bench_function("forward then coast", |b| {
+        b.iter(|| {
+            black_box(bridge.forward()).unwrap();
+            black_box(bridge.coast()).unwrap();
+        })
+    });
+
+    bridge.set_always_write(true);
+
+    c.bench_function("repeated forward", |b| b.iter(|| black_box(bridge.forward()).unwrap()));
+
+    bridge.set_always_write(false);
+
+    c.bench_function("skipped forward", |b| b.iter(|| black_box(bridge.forward()).unwrap()));
+}
+
+criterion_group!(benches, movement);
+criterion_main!(benches);
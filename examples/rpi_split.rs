@@ -0,0 +1,57 @@
+//! Drives two motors in split mode from a Raspberry Pi using the GPIO character device.
+//!
+//! Wiring (BCM numbering): IN1 → 17, IN2 → 18, IN3 → 22, IN4 → 23 and nSLEEP → 24.
+//!
+//! ```sh
+//! cargo run --example rpi_split
+//! ```
+
+use std::error::Error;
+
+use embedded_hal::delay::DelayNs;
+use linux_embedded_hal::gpio_cdev::{Chip, LineRequestFlags};
+use linux_embedded_hal::{CdevPin, Delay};
+
+use drv8833_driver::{Breaks, MotorDriver, Movement};
+
+fn output(chip: &mut Chip, offset: u32) -> Result<CdevPin, Box<dyn Error>> {
+    let handle = chip
+        .get_line(offset)?
+        .request(LineRequestFlags::OUTPUT, 0, "drv8833")?;
+
+    Ok(CdevPin::new(handle)?)
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let mut chip = Chip::new("/dev/gpiochip0")?;
+
+    let in1 = output(&mut chip, 17)?;
+    let in2 = output(&mut chip, 18)?;
+    let in3 = output(&mut chip, 22)?;
+    let in4 = output(&mut chip, 23)?;
+    let sleep = output(&mut chip, 24)?;
+
+    let mut motor = MotorDriver::new_split(in1, in2, in3, in4, Some(sleep), None::<CdevPin>);
+    let mut delay = Delay;
+
+    motor.wakeup()?;
+
+    motor.a.forward()?;
+    motor.b.forward()?;
+    delay.delay_ms(1_000);
+
+    motor.a.coast()?;
+    motor.b.coast()?;
+    delay.delay_ms(500);
+
+    motor.a.reverse()?;
+    motor.b.reverse()?;
+    delay.delay_ms(1_000);
+
+    motor.a.stop()?;
+    motor.b.stop()?;
+
+    motor.sleep()?;
+
+    Ok(())
+}
@@ -0,0 +1,281 @@
+use embedded_hal::digital::OutputPin;
+
+use crate::driver::{check_percent, Breaks, CurrentLoad, Driver, MotorDriverError, Movement, PwmMovement};
+use crate::parallel_driver::ParallelDriver;
+use crate::split_driver::SplitDriver;
+
+/// Holds any of the digital modes sharing the same pin types, for applications selecting the mode at
+/// runtime, e.g. from a configuration file, which would otherwise need a different field type per mode.
+///
+/// Commands are dispatched to the wrapped driver. In split mode [Movement] and [Breaks] command both
+/// bridges together, e.g. driving a two motor robot straight, while bridge specific commands remain
+/// available by matching on the variant. Operations the active mode can't perform, such as speed
+/// control on a digital mode, fail with [MotorDriverError::Unsupported] instead, while [DynMotor](crate::DynMotor) keeps
+/// its contract of driving the digital modes at full speed for any non-zero percentage.
+///
+/// # Example
+///
+/// ```no_run
+/// use drv8833_driver::{AnyDriver, MotorDriver, Movement, ParallelDriver, SplitDriver};
+/// use embedded_hal_mock::eh1::pin::Mock as Pin;
+///
+/// let (in1, in2, in3, in4) = (Pin::new(&[]), Pin::new(&[]), Pin::new(&[]), Pin::new(&[]));
+/// let (sleep, parallel) = (Pin::new(&[]), true);
+///
+/// let driver: AnyDriver<_, _, _, _> = match parallel {
+///     true => ParallelDriver::new(in1, in2, in3, in4).into(),
+///     false => SplitDriver::new(in1, in2, in3, in4).into(),
+/// };
+///
+/// let mut motor = MotorDriver::from_driver(driver, Some(sleep), None::<Pin>);
+///
+/// motor.forward()?;
+/// # Ok::<(), drv8833_driver::MotorDriverError>(())
+/// ```
+pub enum AnyDriver<IN1, IN2, IN3, IN4>
+    where
+        IN1: OutputPin,
+        IN2: OutputPin,
+        IN3: OutputPin,
+        IN4: OutputPin,
+{
+    Split(SplitDriver<IN1, IN2, IN3, IN4>),
+    Parallel(ParallelDriver<IN1, IN2, IN3, IN4>),
+}
+
+impl<IN1, IN2, IN3, IN4> From<SplitDriver<IN1, IN2, IN3, IN4>> for AnyDriver<IN1, IN2, IN3, IN4>
+    where
+        IN1: OutputPin,
+        IN2: OutputPin,
+        IN3: OutputPin,
+        IN4: OutputPin,
+{
+    fn from(driver: SplitDriver<IN1, IN2, IN3, IN4>) -> Self {
+        AnyDriver::Split(driver)
+    }
+}
+
+impl<IN1, IN2, IN3, IN4> From<ParallelDriver<IN1, IN2, IN3, IN4>> for AnyDriver<IN1, IN2, IN3, IN4>
+    where
+        IN1: OutputPin,
+        IN2: OutputPin,
+        IN3: OutputPin,
+        IN4: OutputPin,
+{
+    fn from(driver: ParallelDriver<IN1, IN2, IN3, IN4>) -> Self {
+        AnyDriver::Parallel(driver)
+    }
+}
+
+impl<IN1, IN2, IN3, IN4> Driver for AnyDriver<IN1, IN2, IN3, IN4>
+    where
+        IN1: OutputPin,
+        IN2: OutputPin,
+        IN3: OutputPin,
+        IN4: OutputPin,
+{}
+
+impl<IN1, IN2, IN3, IN4> Movement for AnyDriver<IN1, IN2, IN3, IN4>
+    where
+        IN1: OutputPin,
+        IN2: OutputPin,
+        IN3: OutputPin,
+        IN4: OutputPin,
+{
+    fn forward(&mut self) -> Result<(), MotorDriverError> {
+        match self {
+            AnyDriver::Split(driver) => {
+                driver.a.forward()?;
+                driver.b.forward()
+            }
+            AnyDriver::Parallel(driver) => driver.forward(),
+        }
+    }
+
+    fn reverse(&mut self) -> Result<(), MotorDriverError> {
+        match self {
+            AnyDriver::Split(driver) => {
+                driver.a.reverse()?;
+                driver.b.reverse()
+            }
+            AnyDriver::Parallel(driver) => driver.reverse(),
+        }
+    }
+}
+
+/// The digital modes can only coast or run at full speed, so any percentage between `1` and `99` returns
+/// [MotorDriverError::Unsupported] without touching the pins, and one above 100 [MotorDriverError::InvalidRange].
+impl<IN1, IN2, IN3, IN4> PwmMovement for AnyDriver<IN1, IN2, IN3, IN4>
+    where
+        IN1: OutputPin,
+        IN2: OutputPin,
+        IN3: OutputPin,
+        IN4: OutputPin,
+{
+    fn forward(&mut self, percent: u8) -> Result<(), MotorDriverError> {
+        check_percent(percent);
+
+        match percent {
+            0 => Breaks::coast(self),
+            100 => Movement::forward(self),
+            1..=99 => Err(MotorDriverError::Unsupported),
+            _ => Err(MotorDriverError::InvalidRange),
+        }
+    }
+
+    fn reverse(&mut self, percent: u8) -> Result<(), MotorDriverError> {
+        check_percent(percent);
+
+        match percent {
+            0 => Breaks::coast(self),
+            100 => Movement::reverse(self),
+            1..=99 => Err(MotorDriverError::Unsupported),
+            _ => Err(MotorDriverError::InvalidRange),
+        }
+    }
+}
+
+/// Drives the digital modes at full speed for any non-zero percentage, like [Bridge](crate::Bridge) and
+/// [ParallelDriver], rather than rejecting it as [PwmMovement] does.
+impl<IN1, IN2, IN3, IN4> crate::driver::DynMotor for AnyDriver<IN1, IN2, IN3, IN4>
+    where
+        IN1: OutputPin,
+        IN2: OutputPin,
+        IN3: OutputPin,
+        IN4: OutputPin,
+{
+    fn forward(&mut self, percent: u8) -> Result<(), MotorDriverError> {
+        check_percent(percent);
+
+        match percent {
+            0 => Breaks::coast(self),
+            1..=100 => Movement::forward(self),
+            _ => Err(MotorDriverError::InvalidRange),
+        }
+    }
+
+    fn reverse(&mut self, percent: u8) -> Result<(), MotorDriverError> {
+        check_percent(percent);
+
+        match percent {
+            0 => Breaks::coast(self),
+            1..=100 => Movement::reverse(self),
+            _ => Err(MotorDriverError::InvalidRange),
+        }
+    }
+
+    fn coast(&mut self) -> Result<(), MotorDriverError> {
+        Breaks::coast(self)
+    }
+
+    fn stop(&mut self) -> Result<(), MotorDriverError> {
+        Breaks::stop(self)
+    }
+}
+
+impl<IN1, IN2, IN3, IN4> Breaks for AnyDriver<IN1, IN2, IN3, IN4>
+    where
+        IN1: OutputPin,
+        IN2: OutputPin,
+        IN3: OutputPin,
+        IN4: OutputPin,
+{
+    fn coast(&mut self) -> Result<(), MotorDriverError> {
+        match self {
+            AnyDriver::Split(driver) => driver.coast(),
+            AnyDriver::Parallel(driver) => driver.coast(),
+        }
+    }
+
+    fn stop(&mut self) -> Result<(), MotorDriverError> {
+        match self {
+            AnyDriver::Split(driver) => driver.stop(),
+            AnyDriver::Parallel(driver) => driver.stop(),
+        }
+    }
+}
+
+impl<IN1, IN2, IN3, IN4> CurrentLoad for AnyDriver<IN1, IN2, IN3, IN4>
+    where
+        IN1: OutputPin,
+        IN2: OutputPin,
+        IN3: OutputPin,
+        IN4: OutputPin,
+{
+    fn load_percent(&self) -> u16 {
+        match self {
+            AnyDriver::Split(driver) => driver.load_percent(),
+            AnyDriver::Parallel(driver) => driver.load_percent(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use embedded_hal_mock::eh1::pin::Mock as Pin;
+    use embedded_hal_mock::eh1::pin::State::{High, Low};
+
+    use crate::any_driver::AnyDriver;
+    use crate::driver::{Breaks, MotorDriver, MotorDriverError, Movement};
+    use crate::mock_pins::MockPins;
+    use crate::parallel_driver::ParallelDriver;
+    use crate::split_driver::SplitDriver;
+
+    #[test]
+    fn test_the_mode_can_be_selected_at_runtime() -> Result<(), MotorDriverError> {
+        for parallel in [false, true] {
+            let mut pins = MockPins::new([&[High, Low], &[Low, Low], &[High, Low], &[Low, Low]]);
+            let [in1, in2, in3, in4] = pins.ins();
+
+            let driver: AnyDriver<_, _, _, _> = match parallel {
+                true => ParallelDriver::new(in1, in2, in3, in4).into(),
+                false => SplitDriver::new(in1, in2, in3, in4).into(),
+            };
+
+            let mut motor = MotorDriver::from_driver(driver, None::<Pin>, None::<Pin>);
+
+            motor.forward()?;
+            motor.coast()?;
+
+            assert_eq!(matches!(*motor, AnyDriver::Parallel(_)), parallel);
+
+            pins.done();
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_speed_control_is_unsupported_on_digital_modes() -> Result<(), MotorDriverError> {
+        let mut pins = MockPins::new([&[High], &[Low], &[High], &[Low]]);
+        let [in1, in2, in3, in4] = pins.ins();
+
+        let mut driver: AnyDriver<_, _, _, _> = ParallelDriver::new(in1, in2, in3, in4).into();
+
+        assert!(matches!(crate::driver::PwmMovement::forward(&mut driver, 50), Err(MotorDriverError::Unsupported)));
+
+        crate::driver::PwmMovement::forward(&mut driver, 100)?;
+
+        pins.done();
+
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(not(all(feature = "panic-on-invalid", debug_assertions)))]
+    fn test_dyn_motor_drives_digital_modes_at_full_speed() -> Result<(), MotorDriverError> {
+        let mut pins = MockPins::new([&[High], &[Low], &[High], &[Low]]);
+        let [in1, in2, in3, in4] = pins.ins();
+
+        let driver: AnyDriver<_, _, _, _> = ParallelDriver::new(in1, in2, in3, in4).into();
+        let mut motor: Box<dyn crate::driver::DynMotor> = Box::new(MotorDriver::from_driver(driver, None::<Pin>, None::<Pin>));
+
+        motor.forward(50)?;
+
+        assert!(matches!(motor.forward(150), Err(MotorDriverError::InvalidRange)));
+
+        pins.done();
+
+        Ok(())
+    }
+}
@@ -1,119 +1,596 @@
-use embedded_hal::digital::OutputPin;
+use embedded_hal::delay::DelayNs;
+use embedded_hal::digital::{OutputPin, PinState};
 use embedded_hal::pwm::SetDutyCycle;
 
-use crate::driver::{Breaks, MotorDriverError, Movement, PwmMovement};
+use crate::config::DriverConfig;
+use crate::driver::{check_percent, BrakePolicy, Breaks, Decay, Direction, Driver, MotorDriverError, MotorState, Movement, PwmMovement};
 
-pub fn remap(value: u8, min: u16, max: u16) -> u16 {
-    let percentage = value as f32 / 100.0;
+/// How a speed percentage scaled onto a duty cycle is rounded when it falls between two duty values.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RoundMode {
+    /// Rounds down, so every duty is biased slightly low.
+    Floor,
+    /// Rounds to the closest duty value, e.g. `49.6` to `50`.
+    #[default]
+    Nearest,
+}
+
+/// Maps a speed percentage onto a duty cycle.
+///
+/// `0` is always fully off, regardless of `min`, and `1..=100` is scaled linearly from just above
+/// `min` up to exactly `max`, so `min` only acts as a floor for non-zero speeds and there is no jump
+/// between `99` and `100`. Percentages above `100` are clamped to `max`. Values falling between two
+/// duties are rounded according to `round`.
+///
+/// Every PWM path passes its `max_duty` ceiling as `max` when one is set, see [PwmBridge] for the resulting rules.
+pub fn remap(value: u8, min: u16, max: u16, round: RoundMode) -> u16 {
+    if value == 0 {
+        return 0;
+    }
+
+    let percentage = value.min(100) as f32 / 100.0;
     let min = min as f32;
     let max = max as f32;
 
-    (percentage * (max - min) + min) as u16
+    let duty = percentage * (max - min) + min;
+
+    match round {
+        RoundMode::Floor => duty as u16,
+        // `f32::round` needs std, the duty is never negative so truncating half up rounds it the same way.
+        RoundMode::Nearest => (duty + 0.5) as u16,
+    }
+}
+
+/// A maximum duty must stay above the minimum duty of both directions.
+fn check_max_duty(duty: u16, min_forward: u16, min_reverse: u16) -> Result<(), MotorDriverError> {
+    match duty > min_forward.max(min_reverse) {
+        true => Ok(()),
+        false => Err(MotorDriverError::InvalidRange),
+    }
+}
+
+/// A minimum duty must stay below the maximum duty, when one is set.
+pub(crate) fn check_min_duty(forward: u16, reverse: u16, max_duty: Option<u16>) -> Result<(), MotorDriverError> {
+    match max_duty {
+        Some(max) => check_max_duty(max, forward, reverse),
+        None => Ok(()),
+    }
 }
 
+/// A torque curve must never decrease with the duty and be non-zero at 100%.
+fn check_torque_curve(curve: &[u16; TORQUE_CURVE_POINTS]) -> Result<(), MotorDriverError> {
+    match curve.windows(2).any(|pair| pair[1] < pair[0]) || curve[TORQUE_CURVE_POINTS - 1] == 0 {
+        true => Err(MotorDriverError::InvalidRange),
+        false => Ok(()),
+    }
+}
+
+/// Number of points of a [torque curve](PwmBridge::set_torque_curve), measured every 10% of duty from 0% to 100%.
+pub const TORQUE_CURVE_POINTS: usize = 11;
+
+/// Drives a motor with a PWM signal on IN1 or IN2, depending on the direction and [Decay].
+///
+/// Speed percentages are mapped onto duty cycles the same way by every PWM path, this bridge,
+/// [PwmSplitDriver::drive_combined](crate::PwmSplitDriver::drive_combined) and [PwmParallelDriver](crate::PwmParallelDriver):
+///
+/// - `0` turns the PWM fully off, ignoring `min_duty`, which coasts the motor in both decay modes.
+/// - `100` applies the `max_duty` ceiling if set, or the full duty otherwise.
+/// - Anything in between is scaled linearly from just above `min_duty` up to the ceiling or full duty.
 pub struct PwmBridge<IN1, IN2> {
     bridge: Bridge<IN1, IN2>,
-    min_duty: u16,
+    min_duty_forward: u16,
+    min_duty_reverse: u16,
+    max_duty: Option<u16>,
+    deadband: u8,
+    decay: Decay,
+    speed_constant: u16,
+    min_on_time: Option<(u32, u32)>,
+    torque_curve: Option<[u16; TORQUE_CURVE_POINTS]>,
+    round: RoundMode,
+    raw_duty: u16,
 }
 
 /// Holds the reference to each pin used to drive the motor forward or reverse.
 pub struct Bridge<IN1, IN2> {
     in1: IN1,
     in2: IN2,
+    state: MotorState,
+    synced: bool,
+    always_write: bool,
+    clockwise: Direction,
+    last_direction: Direction,
+    applied: bool,
+    brake_policy: BrakePolicy,
 }
 
 impl<IN1: SetDutyCycle, IN2: SetDutyCycle> PwmMovement for PwmBridge<IN1, IN2> {
+    /// A zero percentage coasts the motor, regardless of the `min_duty` and the decay mode, which
+    /// would otherwise brake it. Returns [MotorDriverError::InvalidRange] above 100.
     fn forward(&mut self, percent: u8) -> Result<(), MotorDriverError> {
-        let percent = remap(percent, self.min_duty, self.bridge.in1.max_duty_cycle());
+        check_percent(percent);
 
-        self.bridge
-            .in1
-            .set_duty_cycle(percent)
-            .map_err(|_| MotorDriverError::UnableToSetDuty)?;
+        if percent > 100 {
+            return Err(MotorDriverError::InvalidRange);
+        }
 
-        self.bridge
-            .in2
-            .set_duty_cycle_fully_off()
-            .map_err(|_| MotorDriverError::UnableToSetDuty)?;
+        if percent == 0 {
+            return self.coast();
+        }
 
-        Ok(())
+        if self.bridge.skip_redundant(MotorState::Forward(percent)) {
+            return Ok(());
+        }
+
+        let max = self.max_duty_cycle(Direction::Forward);
+
+        let duty = self.snap_on_time(remap(percent, self.min_duty_forward, self.ceiling(max), self.round), max);
+
+        self.apply_duty(Direction::Forward, percent, duty, max)
     }
 
+    /// A zero percentage coasts the motor, see [PwmBridge::forward].
     fn reverse(&mut self, percent: u8) -> Result<(), MotorDriverError> {
-        let percent = remap(percent, self.min_duty, self.bridge.in2.max_duty_cycle());
+        check_percent(percent);
 
-        self.bridge
-            .in1
-            .set_duty_cycle_fully_off()
-            .map_err(|_| MotorDriverError::UnableToSetDuty)?;
+        if percent > 100 {
+            return Err(MotorDriverError::InvalidRange);
+        }
+
+        if percent == 0 {
+            return self.coast();
+        }
+
+        if self.bridge.skip_redundant(MotorState::Reverse(percent)) {
+            return Ok(());
+        }
+
+        let max = self.max_duty_cycle(Direction::Reverse);
+
+        let duty = self.snap_on_time(remap(percent, self.min_duty_reverse, self.ceiling(max), self.round), max);
 
-        self.bridge
-            .in2
-            .set_duty_cycle(percent)
-            .map_err(|_| MotorDriverError::UnableToSetDuty)?;
+        self.apply_duty(Direction::Reverse, percent, duty, max)
+    }
+}
+
+impl<IN1: SetDutyCycle, IN2: SetDutyCycle> PwmBridge<IN1, IN2> {
+    /// The maximum duty of the pin carrying the PWM signal for the given direction and current decay.
+    pub(crate) fn max_duty_cycle(&self, direction: Direction) -> u16 {
+        match (direction, self.decay) {
+            (Direction::Forward, Decay::Fast) | (Direction::Reverse, Decay::Slow) => self.bridge.in1.max_duty_cycle(),
+            (Direction::Reverse, Decay::Fast) | (Direction::Forward, Decay::Slow) => self.bridge.in2.max_duty_cycle(),
+        }
+    }
+
+    /// Writes an already remapped `duty` out of `max` to the pins, according to the current decay.
+    ///
+    /// If any write fails, both pins are turned fully off so a partial failure leaves the bridge
+    /// coasted rather than half-driven.
+    pub(crate) fn apply_duty(&mut self, direction: Direction, percent: u8, duty: u16, max: u16) -> Result<(), MotorDriverError> {
+        self.bridge.synced = false;
+
+        if let Err(error) = self.write_duty(direction, duty, max) {
+            let in1 = self.bridge.in1.set_duty_cycle_fully_off();
+            let in2 = self.bridge.in2.set_duty_cycle_fully_off();
+
+            if in1.is_ok() && in2.is_ok() {
+                self.bridge.commit(MotorState::Coast);
+                self.raw_duty = 0;
+            }
+
+            return Err(error);
+        }
+
+        self.raw_duty = match self.decay {
+            Decay::Fast => duty,
+            Decay::Slow => max - duty,
+        };
+
+        self.bridge.commit(match direction {
+            Direction::Forward => MotorState::Forward(percent),
+            Direction::Reverse => MotorState::Reverse(percent),
+        });
+
+        Ok(())
+    }
+
+    fn write_duty(&mut self, direction: Direction, duty: u16, max: u16) -> Result<(), MotorDriverError> {
+        let in1 = &mut self.bridge.in1;
+        let in2 = &mut self.bridge.in2;
+
+        match (direction, self.decay) {
+            (Direction::Forward, Decay::Fast) => {
+                in1.set_duty_cycle(duty).map_err(|_| MotorDriverError::UnableToSetDuty)?;
+                in2.set_duty_cycle_fully_off().map_err(|_| MotorDriverError::UnableToSetDuty)?;
+            }
+            (Direction::Forward, Decay::Slow) => {
+                in1.set_duty_cycle_fully_on().map_err(|_| MotorDriverError::UnableToSetDuty)?;
+                in2.set_duty_cycle(max - duty).map_err(|_| MotorDriverError::UnableToSetDuty)?;
+            }
+            (Direction::Reverse, Decay::Fast) => {
+                in1.set_duty_cycle_fully_off().map_err(|_| MotorDriverError::UnableToSetDuty)?;
+                in2.set_duty_cycle(duty).map_err(|_| MotorDriverError::UnableToSetDuty)?;
+            }
+            (Direction::Reverse, Decay::Slow) => {
+                in1.set_duty_cycle(max - duty).map_err(|_| MotorDriverError::UnableToSetDuty)?;
+                in2.set_duty_cycle_fully_on().map_err(|_| MotorDriverError::UnableToSetDuty)?;
+            }
+        }
 
         Ok(())
     }
 }
 
 impl<IN1: SetDutyCycle, IN2: SetDutyCycle> Breaks for PwmBridge<IN1, IN2> {
+    /// Releases the pins in the order given by [Bridge::coast], so leaving a brake never briefly
+    /// drives the motor against its last direction.
     fn coast(&mut self) -> Result<(), MotorDriverError> {
-        self.bridge
-            .in1
-            .set_duty_cycle_fully_off()
-            .map_err(|_| MotorDriverError::GpioError)?;
+        self.bridge.synced = false;
+
+        if self.bridge.release_in2_first() {
+            self.bridge.in2.set_duty_cycle_fully_off().map_err(|_| MotorDriverError::GpioError)?;
+            self.bridge.in1.set_duty_cycle_fully_off().map_err(|_| MotorDriverError::GpioError)?;
+        } else {
+            self.bridge.in1.set_duty_cycle_fully_off().map_err(|_| MotorDriverError::GpioError)?;
+            self.bridge.in2.set_duty_cycle_fully_off().map_err(|_| MotorDriverError::GpioError)?;
+        }
 
-        self.bridge
-            .in2
-            .set_duty_cycle_fully_off()
-            .map_err(|_| MotorDriverError::GpioError)?;
+        self.bridge.commit(MotorState::Coast);
+        self.raw_duty = 0;
 
         Ok(())
     }
 
+    /// Follows the [BrakePolicy] of the bridge, see [PwmBridge::set_brake_policy].
     fn stop(&mut self) -> Result<(), MotorDriverError> {
+        if !self.bridge.brake_allowed()? {
+            return self.coast();
+        }
+
+        self.bridge.synced = false;
+
         self.bridge.in1.set_duty_cycle_fully_on().map_err(|_| MotorDriverError::GpioError)?;
         self.bridge.in2.set_duty_cycle_fully_on().map_err(|_| MotorDriverError::GpioError)?;
 
+        self.bridge.commit(MotorState::Stop);
+
         Ok(())
     }
 }
 
+impl<IN1: OutputPin, IN2: OutputPin> Driver for Bridge<IN1, IN2> {}
+
 impl<IN1: OutputPin, IN2: OutputPin> Breaks for Bridge<IN1, IN2> {
+    /// Both pins can't change at once, so leaving a brake passes through a driving state for the
+    /// duration of a single pin write. The pins are released in the order that makes this intermediate
+    /// state the last direction the motor was driven in, which it may still be spinning in, so it
+    /// briefly pushes along with the rotation instead of plugging against it.
+    ///
+    /// From any other state one pin is already low and the transition is glitch-free.
+    #[inline]
     fn coast(&mut self) -> Result<(), MotorDriverError> {
-        self.in1.set_low().map_err(|_| MotorDriverError::GpioError)?;
-        self.in2.set_low().map_err(|_| MotorDriverError::GpioError)?;
+        self.synced = false;
+
+        if self.release_in2_first() {
+            self.in2.set_low().map_err(|_| MotorDriverError::GpioError)?;
+            self.in1.set_low().map_err(|_| MotorDriverError::GpioError)?;
+        } else {
+            self.in1.set_low().map_err(|_| MotorDriverError::GpioError)?;
+            self.in2.set_low().map_err(|_| MotorDriverError::GpioError)?;
+        }
+
+        self.commit(MotorState::Coast);
 
         Ok(())
     }
 
+    /// Follows the [BrakePolicy], see [Bridge::set_brake_policy].
+    #[inline]
     fn stop(&mut self) -> Result<(), MotorDriverError> {
+        if !self.brake_allowed()? {
+            return self.coast();
+        }
+
+        self.synced = false;
+
         self.in1.set_high().map_err(|_| MotorDriverError::GpioError)?;
         self.in2.set_high().map_err(|_| MotorDriverError::GpioError)?;
 
+        self.commit(MotorState::Stop);
+
         Ok(())
     }
 }
 
 impl<IN1: OutputPin, IN2: OutputPin> Movement for Bridge<IN1, IN2> {
+    #[inline]
     fn forward(&mut self) -> Result<(), MotorDriverError> {
-        self.in1.set_high().map_err(|_| MotorDriverError::GpioError)?;
-        self.in2.set_low().map_err(|_| MotorDriverError::GpioError)?;
-
-        Ok(())
+        self.write_or_coast(PinState::High, PinState::Low, MotorState::Forward(100))
     }
 
+    #[inline]
     fn reverse(&mut self) -> Result<(), MotorDriverError> {
-        self.in1.set_low().map_err(|_| MotorDriverError::GpioError)?;
-        self.in2.set_high().map_err(|_| MotorDriverError::GpioError)?;
+        self.write_or_coast(PinState::Low, PinState::High, MotorState::Reverse(100))
+    }
+}
 
-        Ok(())
+impl<IN1: SetDutyCycle, IN2: SetDutyCycle> crate::driver::DynMotor for PwmBridge<IN1, IN2> {
+    fn forward(&mut self, percent: u8) -> Result<(), MotorDriverError> {
+        PwmMovement::forward(self, percent)
+    }
+
+    fn reverse(&mut self, percent: u8) -> Result<(), MotorDriverError> {
+        PwmMovement::reverse(self, percent)
+    }
+
+    fn coast(&mut self) -> Result<(), MotorDriverError> {
+        Breaks::coast(self)
+    }
+
+    fn stop(&mut self) -> Result<(), MotorDriverError> {
+        Breaks::stop(self)
+    }
+}
+
+impl<IN1: OutputPin, IN2: OutputPin> crate::driver::DynMotor for Bridge<IN1, IN2> {
+    fn forward(&mut self, percent: u8) -> Result<(), MotorDriverError> {
+        self.forward_percent(percent)
+    }
+
+    fn reverse(&mut self, percent: u8) -> Result<(), MotorDriverError> {
+        self.reverse_percent(percent)
+    }
+
+    fn coast(&mut self) -> Result<(), MotorDriverError> {
+        Breaks::coast(self)
+    }
+
+    fn stop(&mut self) -> Result<(), MotorDriverError> {
+        Breaks::stop(self)
     }
 }
 
 impl<IN1, IN2> Bridge<IN1, IN2> {
     pub fn new(in1: IN1, in2: IN2) -> Self {
-        Self { in1, in2 }
+        Self {
+            in1,
+            in2,
+            state: MotorState::default(),
+            synced: false,
+            always_write: false,
+            clockwise: Direction::Forward,
+            last_direction: Direction::Forward,
+            applied: false,
+            brake_policy: BrakePolicy::default(),
+        }
+    }
+
+    /// Makes every movement command write the pins, even when repeating the state they are already
+    /// in, e.g. to keep feeding a hardware watchdog. By default such commands are skipped.
+    pub fn set_always_write(&mut self, always_write: bool) {
+        self.always_write = always_write;
+    }
+
+    /// Sets what [Breaks::stop] and [Bridge::brake_low_side] do, e.g. [BrakePolicy::Coast] for a geartrain
+    /// which can't tolerate active braking. [Bridge::set_raw] is left unrestricted.
+    pub fn set_brake_policy(&mut self, policy: BrakePolicy) {
+        self.brake_policy = policy;
+    }
+
+    /// Returns the current brake policy.
+    pub fn brake_policy(&self) -> BrakePolicy {
+        self.brake_policy
+    }
+
+    /// Whether a brake command should brake, `false` when it should coast instead, following the
+    /// [BrakePolicy].
+    pub(crate) fn brake_allowed(&self) -> Result<bool, MotorDriverError> {
+        match self.brake_policy {
+            BrakePolicy::Brake => Ok(true),
+            BrakePolicy::Coast => Ok(false),
+            BrakePolicy::Reject => Err(MotorDriverError::Unsupported),
+        }
+    }
+
+    /// Whether the pins are known to already be in `state`, so writing them again can be skipped. A
+    /// skipped command is recorded as not [applied](Bridge::last_command_applied).
+    #[inline]
+    pub(crate) fn skip_redundant(&mut self, state: MotorState) -> bool {
+        let skip = self.synced && !self.always_write && self.state == state;
+
+        if skip {
+            self.applied = false;
+        }
+
+        skip
+    }
+
+    /// Records a state successfully written to the pins.
+    #[inline]
+    fn commit(&mut self, state: MotorState) {
+        match state {
+            MotorState::Forward(_) => self.last_direction = Direction::Forward,
+            MotorState::Reverse(_) => self.last_direction = Direction::Reverse,
+            MotorState::Coast | MotorState::Stop => {}
+        }
+
+        self.state = state;
+        self.synced = true;
+        self.applied = true;
+    }
+
+    /// Whether coasting from the current state should lower IN2 before IN1. Leaving a brake that way
+    /// passes through forward, otherwise through reverse.
+    #[inline]
+    fn release_in2_first(&self) -> bool {
+        self.state == MotorState::Stop && self.last_direction == Direction::Forward
+    }
+
+    /// Defines which electrical direction turns the motor clockwise, [Direction::Forward] by default.
+    pub fn set_clockwise_direction(&mut self, direction: Direction) {
+        self.clockwise = direction;
+    }
+
+    /// Returns the last state successfully commanded to this bridge.
+    pub fn state(&self) -> MotorState {
+        self.state
+    }
+
+    /// Consumes the bridge and returns its IN1 and IN2 pins, left as they were last driven.
+    pub fn release(self) -> (IN1, IN2) {
+        (self.in1, self.in2)
+    }
+
+    /// Returns whether the last successful command wrote the pins, or was skipped for repeating the state
+    /// they were already in, e.g. to count the actual bus transactions.
+    pub fn last_command_applied(&self) -> bool {
+        self.applied
+    }
+}
+
+impl<IN1: OutputPin, IN2: OutputPin> Bridge<IN1, IN2> {
+    pub(crate) fn set_in1(&mut self, state: PinState) -> Result<(), MotorDriverError> {
+        self.synced = false;
+        self.in1.set_state(state).map_err(|_| MotorDriverError::GpioError)
+    }
+
+    pub(crate) fn set_in2(&mut self, state: PinState) -> Result<(), MotorDriverError> {
+        self.synced = false;
+        self.in2.set_state(state).map_err(|_| MotorDriverError::GpioError)
+    }
+
+    /// Writes IN1 and IN2 to the exact given levels, in that order, bypassing the movement commands, e.g.
+    /// to experiment with custom decay patterns or diagnose wiring. Unlike the movement commands the pins
+    /// are written even if already at these levels.
+    ///
+    /// The state is then tracked from the DRV8833 truth table, so [Bridge::state] still reflects the pins.
+    #[must_use = "motor command result must be checked"]
+    pub fn set_raw(&mut self, in1: PinState, in2: PinState) -> Result<(), MotorDriverError> {
+        self.set_in1(in1)?;
+        self.set_in2(in2)?;
+
+        self.commit(match (in1, in2) {
+            (PinState::Low, PinState::Low) => MotorState::Coast,
+            (PinState::High, PinState::High) => MotorState::Stop,
+            (PinState::High, PinState::Low) => MotorState::Forward(100),
+            (PinState::Low, PinState::High) => MotorState::Reverse(100),
+        });
+
+        Ok(())
+    }
+
+    /// Records a state applied through [Bridge::set_in1] and [Bridge::set_in2].
+    pub(crate) fn record_state(&mut self, state: MotorState) {
+        self.commit(state);
+    }
+
+    /// Drives the pins to the given levels, coasting the bridge if any write fails so a partial
+    /// failure never leaves it half-driven.
+    #[inline]
+    fn write_or_coast(&mut self, in1: PinState, in2: PinState, state: MotorState) -> Result<(), MotorDriverError> {
+        if self.skip_redundant(state) {
+            return Ok(());
+        }
+
+        self.synced = false;
+
+        let result = self
+            .in1
+            .set_state(in1)
+            .map_err(|_| MotorDriverError::GpioError)
+            .and_then(|_| self.in2.set_state(in2).map_err(|_| MotorDriverError::GpioError));
+
+        if let Err(error) = result {
+            let in1 = self.in1.set_low();
+            let in2 = self.in2.set_low();
+
+            if in1.is_ok() && in2.is_ok() {
+                self.commit(MotorState::Coast);
+            }
+
+            return Err(error);
+        }
+
+        self.commit(state);
+
+        Ok(())
+    }
+
+    /// Brakes the motor through the low-side FETs, letting the winding current recirculate (slow decay).
+    ///
+    /// On the DRV8833 the brake state is entered by driving both IN1 and IN2 high, which turns on both
+    /// low-side FETs (OUT1 = OUT2 = L). This is the same pin pattern used by [Breaks::stop]; the explicit
+    /// name exists for code ported from drivers where "stop" brakes through the high side instead.
+    ///
+    /// Follows the [BrakePolicy], like [Breaks::stop].
+    #[must_use = "motor command result must be checked"]
+    pub fn brake_low_side(&mut self) -> Result<(), MotorDriverError> {
+        if !self.brake_allowed()? {
+            return self.coast();
+        }
+
+        self.synced = false;
+
+        self.in1.set_high().map_err(|_| MotorDriverError::GpioError)?;
+        self.in2.set_high().map_err(|_| MotorDriverError::GpioError)?;
+
+        self.commit(MotorState::Stop);
+
+        Ok(())
+    }
+
+    /// Accepts a speed percentage for API parity with [PwmBridge], but since a digital bridge has
+    /// no speed control, `0` coasts the motor and any other value drives it forward at full speed.
+    #[must_use = "motor command result must be checked"]
+    pub fn forward_percent(&mut self, percent: u8) -> Result<(), MotorDriverError> {
+        check_percent(percent);
+
+        match percent {
+            0 => self.coast(),
+            1..=100 => self.forward(),
+            _ => Err(MotorDriverError::InvalidRange),
+        }
+    }
+
+    /// Turns the motor clockwise, as defined by [Bridge::set_clockwise_direction].
+    #[must_use = "motor command result must be checked"]
+    pub fn clockwise(&mut self) -> Result<(), MotorDriverError> {
+        match self.clockwise {
+            Direction::Forward => self.forward(),
+            Direction::Reverse => self.reverse(),
+        }
+    }
+
+    /// Turns the motor counter-clockwise, as defined by [Bridge::set_clockwise_direction].
+    #[must_use = "motor command result must be checked"]
+    pub fn counter_clockwise(&mut self) -> Result<(), MotorDriverError> {
+        match self.clockwise {
+            Direction::Forward => self.reverse(),
+            Direction::Reverse => self.forward(),
+        }
+    }
+
+    /// Reverse counterpart of [Bridge::forward_percent].
+    #[must_use = "motor command result must be checked"]
+    pub fn reverse_percent(&mut self, percent: u8) -> Result<(), MotorDriverError> {
+        check_percent(percent);
+
+        match percent {
+            0 => self.coast(),
+            1..=100 => self.reverse(),
+            _ => Err(MotorDriverError::InvalidRange),
+        }
+    }
+
+    /// Commands the given state, e.g. one previously read from [Bridge::state]. As a digital bridge has
+    /// no speed control, any forward or reverse percentage drives the motor at full speed.
+    #[must_use = "motor command result must be checked"]
+    pub fn set_state(&mut self, state: MotorState) -> Result<(), MotorDriverError> {
+        match state {
+            MotorState::Coast => self.coast(),
+            MotorState::Stop => self.stop(),
+            MotorState::Forward(_) => self.forward(),
+            MotorState::Reverse(_) => self.reverse(),
+        }
     }
 }
 
@@ -121,11 +598,1632 @@ impl<IN1, IN2> PwmBridge<IN1, IN2> {
     pub fn new(in1: IN1, in2: IN2, min_duty: u16) -> Self {
         Self {
             bridge: Bridge::new(in1, in2),
-            min_duty,
+            min_duty_forward: min_duty,
+            min_duty_reverse: min_duty,
+            max_duty: None,
+            deadband: 0,
+            decay: Decay::default(),
+            speed_constant: 0,
+            min_on_time: None,
+            torque_curve: None,
+            round: RoundMode::default(),
+            raw_duty: 0,
+        }
+    }
+
+    /// Sets the same minimum duty for both directions.
+    ///
+    /// Returns [MotorDriverError::InvalidRange] unless the duty is below the [maximum duty](PwmBridge::set_max_duty).
+    pub fn set_min_duty(&mut self, duty: u16) -> Result<(), MotorDriverError> {
+        self.set_min_duty_directional(duty, duty)
+    }
+
+    /// Sets a different minimum duty for each direction, for motors stalling at a different duty
+    /// forward than in reverse, e.g. due to friction asymmetry in a gearbox.
+    ///
+    /// Returns [MotorDriverError::InvalidRange] unless both duties are below the
+    /// [maximum duty](PwmBridge::set_max_duty).
+    pub fn set_min_duty_directional(&mut self, forward: u16, reverse: u16) -> Result<(), MotorDriverError> {
+        check_min_duty(forward, reverse, self.max_duty)?;
+
+        self.bridge.synced = false;
+        self.min_duty_forward = forward;
+        self.min_duty_reverse = reverse;
+
+        Ok(())
+    }
+
+    /// Returns the minimum duty used when driving forward, see [PwmBridge::min_duty_for].
+    pub fn min_duty(&self) -> u16 {
+        self.min_duty_forward
+    }
+
+    /// Returns the minimum duty used when driving in the given direction.
+    pub fn min_duty_for(&self, direction: Direction) -> u16 {
+        match direction {
+            Direction::Forward => self.min_duty_forward,
+            Direction::Reverse => self.min_duty_reverse,
+        }
+    }
+
+    /// Caps the duty applied at 100%, e.g. for supplies which can't handle the motor at full power.
+    /// Speeds are then remapped between `min_duty` and this ceiling instead of the PWM's maximum.
+    ///
+    /// Returns [MotorDriverError::InvalidRange] unless the ceiling is above the `min_duty` of both directions.
+    pub fn set_max_duty(&mut self, duty: u16) -> Result<(), MotorDriverError> {
+        check_max_duty(duty, self.min_duty_forward, self.min_duty_reverse)?;
+
+        self.max_duty = Some(duty);
+        self.bridge.synced = false;
+
+        Ok(())
+    }
+
+    pub fn max_duty(&self) -> Option<u16> {
+        self.max_duty
+    }
+
+    /// Sets the shortest pulse, in nanoseconds, the FETs can fully switch within, along with the period
+    /// of the PWM signal, e.g. `10_000` at 100kHz. Duties whose on-time would be shorter are rounded to
+    /// the nearest of zero and the minimum on-time, so the FETs never linger in their linear region.
+    ///
+    /// Returns [MotorDriverError::InvalidRange] if `period_ns` is zero or shorter than `min_on_ns`.
+    pub fn set_min_on_time_ns(&mut self, min_on_ns: u32, period_ns: u32) -> Result<(), MotorDriverError> {
+        if period_ns == 0 || min_on_ns > period_ns {
+            return Err(MotorDriverError::InvalidRange);
+        }
+
+        self.min_on_time = Some((min_on_ns, period_ns));
+        self.bridge.synced = false;
+
+        Ok(())
+    }
+
+    /// Rounds a `duty` out of `max` whose on-time is below the configured minimum to either zero or
+    /// the minimum on-time, whichever is closest.
+    pub(crate) fn snap_on_time(&self, duty: u16, max: u16) -> u16 {
+        let Some((min_on_ns, period_ns)) = self.min_on_time else {
+            return duty;
+        };
+
+        let min = (max as u64 * min_on_ns as u64).div_ceil(period_ns as u64) as u16;
+
+        match duty {
+            0 => 0,
+            duty if duty >= min => duty,
+            duty if duty as u32 * 2 >= min as u32 => min,
+            _ => 0,
+        }
+    }
+
+    /// The duty representing 100% out of the pin's `max`, honoring the configured ceiling.
+    pub(crate) fn ceiling(&self, max: u16) -> u16 {
+        self.max_duty.map_or(max, |duty| duty.min(max))
+    }
+
+    /// Swaps in new PWM handles, e.g. after reconfiguring their timer, returning the previous ones.
+    /// The bridge configuration is preserved, but the new handles are left untouched until the next command.
+    pub fn replace_pins(&mut self, in1: IN1, in2: IN2) -> (IN1, IN2) {
+        self.bridge.synced = false;
+
+        (
+            core::mem::replace(&mut self.bridge.in1, in1),
+            core::mem::replace(&mut self.bridge.in2, in2),
+        )
+    }
+
+    /// Returns the last state successfully commanded to this bridge.
+    pub fn state(&self) -> MotorState {
+        self.bridge.state
+    }
+
+    /// Returns whether the last successful command wrote the pins, see [Bridge::last_command_applied].
+    pub fn last_command_applied(&self) -> bool {
+        self.bridge.applied
+    }
+
+    /// Defines which electrical direction turns the motor clockwise, [Direction::Forward] by default.
+    pub fn set_clockwise_direction(&mut self, direction: Direction) {
+        self.bridge.clockwise = direction;
+    }
+
+    /// Selects how the winding current decays during the off-time of the PWM signal, taking
+    /// effect on the next movement command.
+    pub fn set_decay(&mut self, decay: Decay) {
+        self.bridge.synced = false;
+        self.decay = decay;
+    }
+
+    /// Returns the current decay mode.
+    pub fn decay(&self) -> Decay {
+        self.decay
+    }
+
+    /// See [Bridge::set_always_write].
+    pub fn set_always_write(&mut self, always_write: bool) {
+        self.bridge.set_always_write(always_write);
+    }
+
+    /// See [Bridge::set_brake_policy].
+    pub fn set_brake_policy(&mut self, policy: BrakePolicy) {
+        self.bridge.set_brake_policy(policy);
+    }
+
+    /// Sets how percentages falling between two duty values are rounded, [RoundMode::Nearest] by default.
+    /// Takes effect on the next movement command.
+    pub fn set_round_mode(&mut self, round: RoundMode) {
+        self.bridge.synced = false;
+        self.round = round;
+    }
+
+    /// Returns the current rounding mode.
+    pub fn round_mode(&self) -> RoundMode {
+        self.round
+    }
+
+    /// Sets the percentage around zero within which [PwmBridge::set_speed] coasts the motor.
+    pub fn set_deadband(&mut self, percent: u8) {
+        self.deadband = percent;
+    }
+
+    /// Returns the current settings, e.g. to apply them to other bridges with [PwmBridge::apply_config].
+    pub fn config(&self) -> DriverConfig {
+        DriverConfig {
+            decay: self.decay,
+            min_duty_forward: self.min_duty_forward,
+            min_duty_reverse: self.min_duty_reverse,
+            max_duty: self.max_duty,
+            deadband: self.deadband,
+            torque_curve: self.torque_curve,
+            clockwise: self.bridge.clockwise,
+            round: self.round,
+            brake_policy: self.bridge.brake_policy,
+        }
+    }
+
+    /// Applies every setting of `config`, taking effect on the next movement command.
+    ///
+    /// Returns [MotorDriverError::InvalidRange], leaving the bridge unchanged, if the maximum duty or the torque
+    /// curve would be rejected by [PwmBridge::set_max_duty] or [PwmBridge::set_torque_curve].
+    pub fn apply_config(&mut self, config: &DriverConfig) -> Result<(), MotorDriverError> {
+        if let Some(duty) = config.max_duty {
+            check_max_duty(duty, config.min_duty_forward, config.min_duty_reverse)?;
+        }
+
+        if let Some(curve) = &config.torque_curve {
+            check_torque_curve(curve)?;
         }
+
+        self.set_decay(config.decay);
+        self.bridge.synced = false;
+        self.min_duty_forward = config.min_duty_forward;
+        self.min_duty_reverse = config.min_duty_reverse;
+        self.max_duty = config.max_duty;
+        self.deadband = config.deadband;
+        self.torque_curve = config.torque_curve;
+        self.bridge.clockwise = config.clockwise;
+        self.set_round_mode(config.round);
+        self.bridge.brake_policy = config.brake_policy;
+
+        Ok(())
+    }
+
+    /// Sets the no-load speed of the motor in RPM per volt of supply, the `Kv` constant found in most
+    /// motor datasheets, used by [PwmBridge::estimate_rpm].
+    pub fn set_speed_constant(&mut self, rpm_per_volt: u16) {
+        self.speed_constant = rpm_per_volt;
+    }
+
+    /// Estimates the motor speed from the last commanded percentage, the supply voltage in millivolts
+    /// and the [speed constant](PwmBridge::set_speed_constant). Returns `0` when coasting or stopped.
+    ///
+    /// This is an open-loop, no-load figure meant for displays, the actual speed drops with the load.
+    pub fn estimate_rpm(&self, supply_mv: u16) -> u16 {
+        let percent = match self.bridge.state {
+            MotorState::Forward(percent) | MotorState::Reverse(percent) => percent,
+            MotorState::Coast | MotorState::Stop => 0,
+        };
+
+        let rpm = self.speed_constant as u64 * supply_mv as u64 * percent as u64 / 100_000;
+
+        rpm.min(u16::MAX as u64) as u16
+    }
+
+    /// Sets the torque measured at 0%, 10%, up to 100% duty, in any unit, used by [PwmBridge::drive_torque]
+    /// to find the duty producing a given share of the maximum torque.
+    ///
+    /// Returns [MotorDriverError::InvalidRange] unless the torque never decreases with the duty and is
+    /// non-zero at 100%.
+    pub fn set_torque_curve(&mut self, curve: [u16; TORQUE_CURVE_POINTS]) -> Result<(), MotorDriverError> {
+        check_torque_curve(&curve)?;
+
+        self.torque_curve = Some(curve);
+
+        Ok(())
     }
 
-    pub fn set_min_duty(&mut self, duty: u16) {
-        self.min_duty = duty;
+    /// Inverts the torque curve, interpolating linearly between its points, to find the duty percentage
+    /// producing `percent` of the maximum torque. Without a curve the torque is assumed to follow the duty.
+    pub(crate) fn duty_for_torque(&self, percent: u8) -> u8 {
+        let percent = percent.min(100);
+
+        let Some(curve) = self.torque_curve else {
+            return percent;
+        };
+
+        let target = curve[TORQUE_CURVE_POINTS - 1] as u32 * percent as u32 / 100;
+
+        if target <= curve[0] as u32 {
+            return 0;
+        }
+
+        let index = curve.iter().position(|&torque| torque as u32 >= target).unwrap_or(TORQUE_CURVE_POINTS - 1);
+        let (low, high) = (curve[index - 1] as u32, curve[index] as u32);
+        let span = high - low;
+
+        (10 * (index as u32 - 1) + (10 * (target - low) + span / 2) / span) as u8
+    }
+}
+
+impl<IN1: SetDutyCycle, IN2: SetDutyCycle> PwmBridge<IN1, IN2> {
+    /// Turns the motor clockwise at the given speed, as defined by [PwmBridge::set_clockwise_direction].
+    #[must_use = "motor command result must be checked"]
+    pub fn cw(&mut self, percent: u8) -> Result<(), MotorDriverError> {
+        match self.bridge.clockwise {
+            Direction::Forward => self.forward(percent),
+            Direction::Reverse => self.reverse(percent),
+        }
+    }
+
+    /// Turns the motor counter-clockwise at the given speed, as defined by [PwmBridge::set_clockwise_direction].
+    #[must_use = "motor command result must be checked"]
+    pub fn ccw(&mut self, percent: u8) -> Result<(), MotorDriverError> {
+        match self.bridge.clockwise {
+            Direction::Forward => self.reverse(percent),
+            Direction::Reverse => self.forward(percent),
+        }
+    }
+
+    /// Commands the given state, e.g. one previously read from [PwmBridge::state].
+    #[must_use = "motor command result must be checked"]
+    pub fn set_state(&mut self, state: MotorState) -> Result<(), MotorDriverError> {
+        match state {
+            MotorState::Coast => self.coast(),
+            MotorState::Stop => self.stop(),
+            MotorState::Forward(percent) => self.forward(percent),
+            MotorState::Reverse(percent) => self.reverse(percent),
+        }
+    }
+
+    /// Drives the motor from a signed value centered on zero, such as a joystick axis, where `scale`
+    /// is the magnitude representing full speed, e.g. `set_speed(-500, 1000)` reverses at 50%.
+    ///
+    /// Values beyond `scale` are clamped to full speed and values within the configured
+    /// [deadband](PwmBridge::set_deadband) coast the motor.
+    #[must_use = "motor command result must be checked"]
+    pub fn set_speed(&mut self, value: i16, scale: i16) -> Result<(), MotorDriverError> {
+        if scale <= 0 {
+            return Err(MotorDriverError::InvalidRange);
+        }
+
+        let percent = (value as i32).unsigned_abs() * 100 / scale as u32;
+        let percent = percent.min(100) as u8;
+
+        if percent <= self.deadband {
+            self.coast()
+        } else if value > 0 {
+            self.forward(percent)
+        } else {
+            self.reverse(percent)
+        }
+    }
+
+    /// Drives the motor forward, clamping `percent` to 100 instead of returning [MotorDriverError::InvalidRange]
+    /// like [PwmBridge::forward], and returns the percentage actually applied, e.g. for UI sliders overshooting
+    /// their range.
+    #[must_use = "motor command result must be checked"]
+    pub fn forward_clamped(&mut self, percent: u8) -> Result<u8, MotorDriverError> {
+        let percent = percent.min(100);
+
+        self.forward(percent)?;
+
+        Ok(percent)
+    }
+
+    /// Reverse counterpart of [PwmBridge::forward_clamped].
+    #[must_use = "motor command result must be checked"]
+    pub fn reverse_clamped(&mut self, percent: u8) -> Result<u8, MotorDriverError> {
+        let percent = percent.min(100);
+
+        self.reverse(percent)?;
+
+        Ok(percent)
+    }
+
+    /// Flips the motor to the given direction at the last commanded speed, e.g. after `forward(40)`,
+    /// `set_direction(Direction::Reverse)` reverses at 40%.
+    ///
+    /// When the motor is coasting or stopped there is no speed to keep and nothing is changed.
+    #[must_use = "motor command result must be checked"]
+    pub fn set_direction(&mut self, direction: Direction) -> Result<(), MotorDriverError> {
+        let percent = match self.bridge.state {
+            MotorState::Forward(percent) | MotorState::Reverse(percent) => percent,
+            MotorState::Coast | MotorState::Stop => return Ok(()),
+        };
+
+        let state = match direction {
+            Direction::Forward => MotorState::Forward(percent),
+            Direction::Reverse => MotorState::Reverse(percent),
+        };
+
+        self.set_state(state)
+    }
+
+    /// Drives the motor from a signed percentage, positive forward and negative in reverse, while `0`
+    /// coasts it. Magnitudes above 100, including `i8::MIN`, are clamped to full speed.
+    #[must_use = "motor command result must be checked"]
+    pub fn drive(&mut self, speed: i8) -> Result<(), MotorDriverError> {
+        let percent = speed.unsigned_abs();
+
+        match speed.signum() {
+            1 => self.forward_clamped(percent).map(|_| ()),
+            -1 => self.reverse_clamped(percent).map(|_| ()),
+            _ => self.coast(),
+        }
+    }
+
+    /// Drives the motor in `direction` like [PwmBridge::forward] and [PwmBridge::reverse], returning the raw
+    /// value passed to `set_duty_cycle` on the pin carrying the PWM signal, after the percentage went through
+    /// the minimum and maximum duty, rounding and on-time snapping, e.g. for logging or closed loop control.
+    ///
+    /// With [Decay::Slow] this is the duty of the low phase, `max - duty`, and `0` when coasting at 0%. A
+    /// command skipped for repeating the current state returns the duty written by the previous one.
+    #[must_use = "motor command result must be checked"]
+    pub fn drive_reporting_duty(&mut self, direction: Direction, percent: u8) -> Result<u16, MotorDriverError> {
+        match direction {
+            Direction::Forward => self.forward(percent)?,
+            Direction::Reverse => self.reverse(percent)?,
+        }
+
+        Ok(self.raw_duty)
+    }
+
+    /// Switches the decay mode and immediately drives the bridge again with the new decay pattern at the
+    /// same speed, e.g. to trade torque at high speed for quieter operation at low speed in the middle of
+    /// a move. A coasting or braked motor is left untouched, as the decay only applies while driving.
+    #[must_use = "motor command result must be checked"]
+    pub fn update_decay_live(&mut self, decay: Decay) -> Result<(), MotorDriverError> {
+        self.set_decay(decay);
+
+        match self.bridge.state {
+            MotorState::Forward(percent) => self.forward(percent),
+            MotorState::Reverse(percent) => self.reverse(percent),
+            MotorState::Coast | MotorState::Stop => Ok(()),
+        }
+    }
+
+    /// Drives the motor at a signed share of its maximum torque, positive forward and negative in reverse,
+    /// selecting the duty from the [torque curve](PwmBridge::set_torque_curve), while `0` coasts it.
+    /// Magnitudes above 100 are clamped to the maximum torque.
+    #[must_use = "motor command result must be checked"]
+    pub fn drive_torque(&mut self, torque: i8) -> Result<(), MotorDriverError> {
+        let percent = self.duty_for_torque(torque.unsigned_abs());
+
+        match torque.signum() {
+            1 => self.forward(percent),
+            -1 => self.reverse(percent),
+            _ => self.coast(),
+        }
+    }
+
+    /// Drives the motor in `direction` at `percent` for exactly `us` microseconds and then coasts it,
+    /// for short dead-reckoning moves where millisecond granularity is too coarse.
+    ///
+    /// If the drive command fails the motor is coasted without waiting.
+    #[must_use = "motor command result must be checked"]
+    pub fn pulse_us<DELAY>(&mut self, direction: Direction, percent: u8, us: u32, delay: &mut DELAY) -> Result<(), MotorDriverError>
+        where
+            DELAY: DelayNs,
+    {
+        let result = match direction {
+            Direction::Forward => self.forward(percent),
+            Direction::Reverse => self.reverse(percent),
+        };
+
+        if result.is_ok() {
+            delay.delay_us(us);
+        }
+
+        self.coast()?;
+
+        result
+    }
+
+    /// Drives the motor forward at `percent` for `us` microseconds and then coasts it, see [PwmBridge::pulse_us].
+    #[must_use = "motor command result must be checked"]
+    pub fn forward_for_us<DELAY: DelayNs>(&mut self, percent: u8, us: u32, delay: &mut DELAY) -> Result<(), MotorDriverError> {
+        self.pulse_us(Direction::Forward, percent, us, delay)
+    }
+
+    /// Reverse counterpart of [PwmBridge::forward_for_us].
+    #[must_use = "motor command result must be checked"]
+    pub fn reverse_for_us<DELAY: DelayNs>(&mut self, percent: u8, us: u32, delay: &mut DELAY) -> Result<(), MotorDriverError> {
+        self.pulse_us(Direction::Reverse, percent, us, delay)
+    }
+
+    /// Drives the motor in `direction` at `percent` until `count` advanced by `ticks`, then brakes it,
+    /// for repeatable moves on motors with encoder feedback. `count` returns the current value of a free
+    /// running encoder counter, which may wrap around.
+    ///
+    /// The counter is polled once every millisecond and the motor is braked regardless once `timeout_ms`
+    /// milliseconds elapsed, e.g. when stalled. Returns whether the target was reached before the timeout.
+    ///
+    /// If the drive command fails the motor is braked without polling. Under [BrakePolicy::Reject] the motor is
+    /// coasted instead and [MotorDriverError::Unsupported] returned.
+    #[must_use = "motor command result must be checked"]
+    pub fn move_ticks<COUNT, DELAY>(
+        &mut self,
+        direction: Direction,
+        percent: u8,
+        ticks: u32,
+        count: &mut COUNT,
+        timeout_ms: u32,
+        delay: &mut DELAY,
+    ) -> Result<bool, MotorDriverError>
+        where
+            COUNT: FnMut() -> u32,
+            DELAY: DelayNs,
+    {
+        let start = count();
+
+        let result = match direction {
+            Direction::Forward => self.forward(percent),
+            Direction::Reverse => self.reverse(percent),
+        };
+
+        let mut reached = false;
+
+        if result.is_ok() {
+            let mut elapsed = 0;
+
+            reached = loop {
+                if count().wrapping_sub(start) >= ticks {
+                    break true;
+                }
+
+                if elapsed == timeout_ms {
+                    break false;
+                }
+
+                delay.delay_ms(1);
+                elapsed += 1;
+            };
+        }
+
+        if let Err(error) = self.stop() {
+            if error == MotorDriverError::Unsupported {
+                self.coast()?;
+            }
+
+            return Err(error);
+        }
+
+        result.map(|_| reached)
+    }
+
+    /// Ramps the speed down from the current percentage in `steps` evenly spaced writes, waiting `step_ms`
+    /// milliseconds after each one, with the last write coasting the motor instead of cutting power
+    /// abruptly, e.g. `ramp_coast(10, ..)` from 100% writes 90%, 80%, down to 10% and then coasts.
+    ///
+    /// The motor is coasted right away when it isn't moving or `steps` is zero.
+    #[must_use = "motor command result must be checked"]
+    pub fn ramp_coast<DELAY: DelayNs>(&mut self, steps: u8, step_ms: u32, delay: &mut DELAY) -> Result<(), MotorDriverError> {
+        let (direction, percent) = match self.bridge.state {
+            MotorState::Forward(percent) => (Direction::Forward, percent as u32),
+            MotorState::Reverse(percent) => (Direction::Reverse, percent as u32),
+            MotorState::Coast | MotorState::Stop => return self.coast(),
+        };
+
+        for step in 1..steps as u32 {
+            let percent = (percent * (steps as u32 - step) / steps as u32) as u8;
+
+            match direction {
+                Direction::Forward => self.forward(percent)?,
+                Direction::Reverse => self.reverse(percent)?,
+            }
+
+            delay.delay_ms(step_ms);
+        }
+
+        self.coast()
+    }
+
+    /// Reverses the direction of rotation without a current spike: the current direction is ramped down
+    /// through [PwmBridge::ramp_coast], the motor coasts for another `step_ms` milliseconds, and the
+    /// opposite direction is then ramped up to `target_percent` in `steps` evenly spaced writes, e.g.
+    /// `reverse_smooth(80, 4, ..)` from forward 80% writes 60%, 40%, 20%, coasts and then reverses at 20%,
+    /// 40%, 60% and 80%.
+    ///
+    /// A motor which isn't moving ramps up opposite to the direction it was last driven in.
+    #[must_use = "motor command result must be checked"]
+    pub fn reverse_smooth<DELAY: DelayNs>(
+        &mut self,
+        target_percent: u8,
+        steps: u8,
+        step_ms: u32,
+        delay: &mut DELAY,
+    ) -> Result<(), MotorDriverError> {
+        if target_percent > 100 {
+            return Err(MotorDriverError::InvalidRange);
+        }
+
+        let direction = match self.bridge.state {
+            MotorState::Forward(_) => Direction::Reverse,
+            MotorState::Reverse(_) => Direction::Forward,
+            MotorState::Coast | MotorState::Stop => match self.bridge.last_direction {
+                Direction::Forward => Direction::Reverse,
+                Direction::Reverse => Direction::Forward,
+            },
+        };
+
+        self.ramp_coast(steps, step_ms, delay)?;
+
+        delay.delay_ms(step_ms);
+
+        let steps = steps.max(1) as u32;
+
+        for step in 1..=steps {
+            let percent = (target_percent as u32 * step / steps) as u8;
+
+            match direction {
+                Direction::Forward => self.forward(percent)?,
+                Direction::Reverse => self.reverse(percent)?,
+            }
+
+            if step < steps {
+                delay.delay_ms(step_ms);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Steps the forward speed from `from` to `to` percent in increments of `step`, waiting `settle_ms`
+    /// milliseconds at each step before calling `f` with the current percentage, e.g. to record the
+    /// motor's response through an ADC. Descending ranges are swept downwards.
+    ///
+    /// The motor is left running at the last step.
+    #[must_use = "motor command result must be checked"]
+    pub fn sweep<DELAY, F>(
+        &mut self,
+        from: u8,
+        to: u8,
+        step: u8,
+        settle_ms: u32,
+        delay: &mut DELAY,
+        mut f: F,
+    ) -> Result<(), MotorDriverError>
+        where
+            DELAY: DelayNs,
+            F: FnMut(u8),
+    {
+        if step == 0 || from > 100 || to > 100 {
+            return Err(MotorDriverError::InvalidRange);
+        }
+
+        let mut percent = from;
+
+        loop {
+            self.forward(percent)?;
+
+            delay.delay_ms(settle_ms);
+
+            f(percent);
+
+            let next = match from <= to {
+                true => percent.checked_add(step).filter(|next| *next <= to),
+                false => percent.checked_sub(step).filter(|next| *next >= to),
+            };
+
+            match next {
+                Some(next) => percent = next,
+                None => return Ok(()),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+    use std::convert::Infallible;
+    use std::io::ErrorKind;
+    use std::rc::Rc;
+    use std::time::Duration;
+
+    use embedded_hal::digital::{ErrorType, OutputPin, PinState};
+    use embedded_hal_mock::eh1::pin::Mock as Pin;
+    use embedded_hal_mock::eh1::pin::State::{High, Low};
+    use embedded_hal_mock::eh1::pin::Transaction;
+    use embedded_hal_mock::eh1::pwm::Mock as PwmPin;
+    use embedded_hal_mock::eh1::pwm::Transaction as PwmPinTransaction;
+    use embedded_hal_mock::eh1::MockError;
+
+    use crate::bridge::{remap, Bridge, PwmBridge, RoundMode};
+    use crate::driver::{BrakePolicy, Breaks, Decay, Direction, MotorDriverError, MotorState, Movement, PwmMovement};
+    use crate::mock_delay::MockDelay;
+
+    /// Records every write into a log shared by both pins, to assert the order across pins.
+    struct LoggedPin(u8, Rc<RefCell<Vec<(u8, PinState)>>>);
+
+    impl ErrorType for LoggedPin {
+        type Error = Infallible;
+    }
+
+    impl OutputPin for LoggedPin {
+        fn set_low(&mut self) -> Result<(), Self::Error> {
+            self.1.borrow_mut().push((self.0, PinState::Low));
+
+            Ok(())
+        }
+
+        fn set_high(&mut self) -> Result<(), Self::Error> {
+            self.1.borrow_mut().push((self.0, PinState::High));
+
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_remap_is_continuous_up_to_the_endpoints() {
+        assert_eq!(remap(0, 200, 1000, RoundMode::Nearest), 0);
+        assert_eq!(remap(1, 200, 1000, RoundMode::Nearest), 208);
+        assert_eq!(remap(99, 200, 1000, RoundMode::Nearest), 992);
+        assert_eq!(remap(100, 200, 1000, RoundMode::Nearest), 1000);
+        assert_eq!(remap(150, 200, 1000, RoundMode::Nearest), 1000);
+    }
+
+    #[test]
+    fn test_remap_rounds_to_the_nearest_duty_by_default() {
+        assert_eq!(remap(62, 0, 80, RoundMode::default()), 50);
+        assert_eq!(remap(62, 0, 80, RoundMode::Floor), 49);
+    }
+
+    #[test]
+    fn test_full_speed_is_capped_at_the_max_duty() -> Result<(), MotorDriverError> {
+        let mut in1 = PwmPin::new(&[PwmPinTransaction::max_duty_cycle(1000), PwmPinTransaction::set_duty_cycle(600)]);
+        let mut in2 = PwmPin::new(&[PwmPinTransaction::set_duty_cycle(0)]);
+
+        let mut bridge = PwmBridge::new(in1.clone(), in2.clone(), 200);
+
+        assert!(matches!(bridge.set_max_duty(200), Err(MotorDriverError::InvalidRange)));
+
+        bridge.set_max_duty(600)?;
+        bridge.set_decay(Decay::Fast);
+        bridge.forward(100)?;
+
+        in1.done();
+        in2.done();
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_a_min_duty_at_or_above_the_max_duty_is_rejected() -> Result<(), MotorDriverError> {
+        let mut in1 = PwmPin::new(&[]);
+        let mut in2 = PwmPin::new(&[]);
+
+        let mut bridge = PwmBridge::new(in1.clone(), in2.clone(), 0);
+
+        bridge.set_max_duty(400)?;
+
+        assert!(matches!(bridge.set_min_duty(600), Err(MotorDriverError::InvalidRange)));
+        assert!(matches!(bridge.set_min_duty_directional(100, 400), Err(MotorDriverError::InvalidRange)));
+        assert_eq!(bridge.min_duty_for(Direction::Reverse), 0);
+
+        bridge.set_min_duty(399)?;
+
+        in1.done();
+        in2.done();
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_a_failed_write_coasts_the_bridge() {
+        let mut in1 = Pin::new(&[Transaction::set(High), Transaction::set(Low)]);
+        let mut in2 = Pin::new(&[
+            Transaction::set(Low).with_error(MockError::Io(ErrorKind::Other)),
+            Transaction::set(Low),
+        ]);
+
+        let mut bridge = Bridge::new(in1.clone(), in2.clone());
+
+        assert!(matches!(bridge.forward(), Err(MotorDriverError::GpioError)));
+        assert!(matches!(bridge.state(), MotorState::Coast));
+
+        in1.done();
+        in2.done();
+    }
+
+    #[test]
+    fn test_a_failed_duty_write_coasts_the_pwm_bridge() {
+        let mut in1 = PwmPin::new(&[
+            PwmPinTransaction::max_duty_cycle(100),
+            PwmPinTransaction::set_duty_cycle(50),
+            PwmPinTransaction::set_duty_cycle(0),
+        ]);
+
+        let mut in2 = PwmPin::new(&[
+            PwmPinTransaction::set_duty_cycle(0).with_error(MockError::Io(ErrorKind::Other)),
+            PwmPinTransaction::set_duty_cycle(0),
+        ]);
+
+        let mut bridge = PwmBridge::new(in1.clone(), in2.clone(), 0);
+
+        bridge.set_decay(Decay::Fast);
+
+        assert!(matches!(bridge.forward(50), Err(MotorDriverError::UnableToSetDuty)));
+        assert!(matches!(bridge.state(), MotorState::Coast));
+
+        in1.done();
+        in2.done();
+    }
+
+    #[test]
+    fn test_brake_then_coast_waits_between_both_commands() -> Result<(), MotorDriverError> {
+        let mut in1 = Pin::new(&[Transaction::set(High), Transaction::set(Low)]);
+        let mut in2 = Pin::new(&[Transaction::set(High), Transaction::set(Low)]);
+
+        let mut bridge = Bridge::new(in1.clone(), in2.clone());
+        let mut delay = MockDelay::new();
+
+        bridge.brake_then_coast(250, &mut delay)?;
+
+        assert_eq!(delay.delays(), &[Duration::from_millis(250)]);
+        assert!(matches!(bridge.state(), MotorState::Coast));
+
+        in1.done();
+        in2.done();
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_brake_then_coast_coasts_when_braking_is_rejected() -> Result<(), MotorDriverError> {
+        let mut in1 = Pin::new(&[Transaction::set(High), Transaction::set(Low)]);
+        let mut in2 = Pin::new(&[Transaction::set(Low), Transaction::set(Low)]);
+
+        let mut bridge = Bridge::new(in1.clone(), in2.clone());
+        let mut delay = MockDelay::new();
+
+        bridge.set_brake_policy(BrakePolicy::Reject);
+        Movement::forward(&mut bridge)?;
+
+        assert_eq!(bridge.brake_then_coast(250, &mut delay), Err(MotorDriverError::Unsupported));
+        assert!(delay.delays().is_empty());
+        assert_eq!(bridge.state(), MotorState::Coast);
+
+        in1.done();
+        in2.done();
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_movement_traits_are_object_safe() -> Result<(), MotorDriverError> {
+        let mut in1 = Pin::new(&[Transaction::set(High), Transaction::set(Low)]);
+        let mut in2 = Pin::new(&[Transaction::set(Low), Transaction::set(Low)]);
+        let mut pwm1 = PwmPin::new(&[PwmPinTransaction::max_duty_cycle(100), PwmPinTransaction::set_duty_cycle(50)]);
+        let mut pwm2 = PwmPin::new(&[PwmPinTransaction::set_duty_cycle(0)]);
+
+        let mut bridge = Bridge::new(in1.clone(), in2.clone());
+        let mut pwm_bridge = PwmBridge::new(pwm1.clone(), pwm2.clone(), 0);
+
+        pwm_bridge.set_decay(Decay::Fast);
+
+        // Fails to compile if a method making these traits unusable as trait objects is added.
+        let movement: &mut dyn Movement = &mut bridge;
+        movement.forward()?;
+
+        let breaks: &mut dyn Breaks = &mut bridge;
+        breaks.coast()?;
+
+        let pwm_movement: &mut dyn PwmMovement = &mut pwm_bridge;
+        pwm_movement.forward(50)?;
+
+        in1.done();
+        in2.done();
+        pwm1.done();
+        pwm2.done();
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_each_direction_can_hold_its_own_min_duty() -> Result<(), MotorDriverError> {
+        let mut in1 = PwmPin::new(&[
+            PwmPinTransaction::max_duty_cycle(100),
+            PwmPinTransaction::set_duty_cycle(28),
+            PwmPinTransaction::set_duty_cycle(0),
+        ]);
+
+        let mut in2 = PwmPin::new(&[
+            PwmPinTransaction::set_duty_cycle(0),
+            PwmPinTransaction::max_duty_cycle(100),
+            PwmPinTransaction::set_duty_cycle(46),
+        ]);
+
+        let mut bridge = PwmBridge::new(in1.clone(), in2.clone(), 0);
+
+        bridge.set_decay(Decay::Fast);
+        bridge.set_min_duty_directional(20, 40)?;
+
+        bridge.forward(10)?;
+        bridge.reverse(10)?;
+
+        in1.done();
+        in2.done();
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_sweep_calls_back_at_each_step() -> Result<(), MotorDriverError> {
+        let mut in1_transactions = Vec::new();
+        let mut in2_transactions = Vec::new();
+
+        in1_transactions.push(PwmPinTransaction::set_duty_cycle(0));
+        in2_transactions.push(PwmPinTransaction::set_duty_cycle(0));
+
+        for duty in (10..=100).step_by(10) {
+            in1_transactions.push(PwmPinTransaction::max_duty_cycle(100));
+            in1_transactions.push(PwmPinTransaction::set_duty_cycle(duty));
+            in2_transactions.push(PwmPinTransaction::set_duty_cycle(0));
+        }
+
+        let mut in1 = PwmPin::new(&in1_transactions);
+        let mut in2 = PwmPin::new(&in2_transactions);
+
+        let mut bridge = PwmBridge::new(in1.clone(), in2.clone(), 0);
+        let mut delay = MockDelay::new();
+        let mut steps = Vec::new();
+
+        bridge.set_decay(Decay::Fast);
+        bridge.sweep(0, 100, 10, 20, &mut delay, |percent| steps.push(percent))?;
+
+        assert_eq!(steps, [0, 10, 20, 30, 40, 50, 60, 70, 80, 90, 100]);
+        assert_eq!(delay.total(), Duration::from_millis(220));
+
+        in1.done();
+        in2.done();
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_repeating_the_current_state_skips_the_writes() -> Result<(), MotorDriverError> {
+        let mut in1 = PwmPin::new(&[
+            PwmPinTransaction::max_duty_cycle(100),
+            PwmPinTransaction::set_duty_cycle(50),
+            PwmPinTransaction::max_duty_cycle(100),
+            PwmPinTransaction::set_duty_cycle(50),
+        ]);
+
+        let mut in2 = PwmPin::new(&[PwmPinTransaction::set_duty_cycle(0), PwmPinTransaction::set_duty_cycle(0)]);
+
+        let mut bridge = PwmBridge::new(in1.clone(), in2.clone(), 0);
+
+        bridge.set_decay(Decay::Fast);
+
+        bridge.forward(50)?;
+
+        assert!(bridge.last_command_applied());
+
+        bridge.forward(50)?;
+
+        assert!(!bridge.last_command_applied());
+
+        bridge.set_always_write(true);
+        bridge.forward(50)?;
+
+        assert!(bridge.last_command_applied());
+
+        in1.done();
+        in2.done();
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_zero_percent_coasts_despite_min_duty() -> Result<(), MotorDriverError> {
+        let mut in1 = PwmPin::new(&[PwmPinTransaction::set_duty_cycle(0), PwmPinTransaction::set_duty_cycle(0)]);
+        let mut in2 = PwmPin::new(&[PwmPinTransaction::set_duty_cycle(0), PwmPinTransaction::set_duty_cycle(0)]);
+
+        let mut bridge = PwmBridge::new(in1.clone(), in2.clone(), 30);
+
+        bridge.set_decay(Decay::Fast);
+        bridge.forward(0)?;
+
+        assert_eq!(bridge.state(), MotorState::Coast);
+
+        bridge.set_decay(Decay::Slow);
+        bridge.reverse(0)?;
+
+        assert_eq!(bridge.state(), MotorState::Coast);
+
+        in1.done();
+        in2.done();
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_low_side_brake_drives_both_inputs_high() -> Result<(), MotorDriverError> {
+        let mut in1 = Pin::new(&[Transaction::set(High)]);
+        let mut in2 = Pin::new(&[Transaction::set(High)]);
+
+        let mut bridge = Bridge::new(in1.clone(), in2.clone());
+
+        bridge.brake_low_side()?;
+
+        in1.done();
+        in2.done();
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_percent_shim_coasts_on_zero_and_drives_full_otherwise() -> Result<(), MotorDriverError> {
+        let mut in1 = Pin::new(&[Transaction::set(Low), Transaction::set(High), Transaction::set(Low)]);
+        let mut in2 = Pin::new(&[Transaction::set(Low), Transaction::set(Low), Transaction::set(High)]);
+
+        let mut bridge = Bridge::new(in1.clone(), in2.clone());
+
+        bridge.forward_percent(0)?;
+        bridge.forward_percent(60)?;
+        bridge.reverse_percent(1)?;
+
+        #[cfg(not(feature = "panic-on-invalid"))]
+        assert!(matches!(bridge.forward_percent(101), Err(MotorDriverError::InvalidRange)));
+
+        in1.done();
+        in2.done();
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_clockwise_follows_the_configured_direction() -> Result<(), MotorDriverError> {
+        let mut in1 = Pin::new(&[Transaction::set(High), Transaction::set(Low)]);
+        let mut in2 = Pin::new(&[Transaction::set(Low), Transaction::set(High)]);
+
+        let mut bridge = Bridge::new(in1.clone(), in2.clone());
+
+        bridge.clockwise()?;
+        bridge.set_clockwise_direction(Direction::Reverse);
+        bridge.clockwise()?;
+
+        in1.done();
+        in2.done();
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_signed_speed_picks_direction_and_coasts_within_deadband() -> Result<(), MotorDriverError> {
+        let mut in1 = PwmPin::new(&[
+            PwmPinTransaction::max_duty_cycle(100),
+            PwmPinTransaction::set_duty_cycle(50),
+            PwmPinTransaction::set_duty_cycle(0),
+            PwmPinTransaction::set_duty_cycle(0),
+        ]);
+
+        let mut in2 = PwmPin::new(&[
+            PwmPinTransaction::set_duty_cycle(0),
+            PwmPinTransaction::set_duty_cycle(0),
+            PwmPinTransaction::max_duty_cycle(100),
+            PwmPinTransaction::set_duty_cycle(100),
+        ]);
+
+        let mut bridge = PwmBridge::new(in1.clone(), in2.clone(), 0);
+
+        bridge.set_decay(Decay::Fast);
+        bridge.set_deadband(5);
+
+        bridge.set_speed(500, 1000)?;
+        bridge.set_speed(-40, 1000)?;
+        bridge.set_speed(i16::MIN, 1000)?;
+
+        in1.done();
+        in2.done();
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_slow_decay_holds_one_input_high_and_inverts_the_other() -> Result<(), MotorDriverError> {
+        let mut in1 = PwmPin::new(&[
+            PwmPinTransaction::max_duty_cycle(100),
+            PwmPinTransaction::set_duty_cycle(100),
+            PwmPinTransaction::max_duty_cycle(100),
+            PwmPinTransaction::set_duty_cycle(60),
+        ]);
+
+        let mut in2 = PwmPin::new(&[
+            PwmPinTransaction::max_duty_cycle(100),
+            PwmPinTransaction::set_duty_cycle(70),
+            PwmPinTransaction::max_duty_cycle(100),
+            PwmPinTransaction::set_duty_cycle(100),
+        ]);
+
+        let mut bridge = PwmBridge::new(in1.clone(), in2.clone(), 0);
+
+        bridge.set_decay(Decay::Slow);
+
+        bridge.forward(30)?;
+        bridge.reverse(40)?;
+
+        in1.done();
+        in2.done();
+
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "default-slow-decay")]
+    fn test_bridges_default_to_slow_decay_with_feature() {
+        let mut in1 = PwmPin::new(&[]);
+        let mut in2 = PwmPin::new(&[]);
+
+        let bridge = PwmBridge::new(in1.clone(), in2.clone(), 0);
+
+        assert!(matches!(bridge.decay(), Decay::Slow));
+
+        in1.done();
+        in2.done();
+    }
+
+    #[test]
+    fn test_drive_clamps_i8_min_to_full_reverse() -> Result<(), MotorDriverError> {
+        let mut in1 = PwmPin::new(&[PwmPinTransaction::set_duty_cycle(0), PwmPinTransaction::set_duty_cycle(0)]);
+        let mut in2 = PwmPin::new(&[
+            PwmPinTransaction::max_duty_cycle(100),
+            PwmPinTransaction::set_duty_cycle(100),
+            PwmPinTransaction::set_duty_cycle(0),
+        ]);
+
+        let mut bridge = PwmBridge::new(in1.clone(), in2.clone(), 0);
+
+        bridge.set_decay(Decay::Fast);
+
+        bridge.drive(i8::MIN)?;
+
+        assert_eq!(bridge.state(), MotorState::Reverse(100));
+
+        bridge.drive(0)?;
+
+        assert_eq!(bridge.state(), MotorState::Coast);
+
+        in1.done();
+        in2.done();
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_ramp_coast_decreases_the_duty_before_coasting() -> Result<(), MotorDriverError> {
+        let mut in1_transactions = vec![];
+        let mut in2_transactions = vec![];
+
+        for duty in (10..=100).rev().step_by(10) {
+            in1_transactions.push(PwmPinTransaction::max_duty_cycle(100));
+            in1_transactions.push(PwmPinTransaction::set_duty_cycle(duty));
+            in2_transactions.push(PwmPinTransaction::set_duty_cycle(0));
+        }
+
+        in1_transactions.push(PwmPinTransaction::set_duty_cycle(0));
+        in2_transactions.push(PwmPinTransaction::set_duty_cycle(0));
+
+        let mut in1 = PwmPin::new(&in1_transactions);
+        let mut in2 = PwmPin::new(&in2_transactions);
+
+        let mut bridge = PwmBridge::new(in1.clone(), in2.clone(), 0);
+        let mut delay = MockDelay::new();
+
+        bridge.set_decay(Decay::Fast);
+
+        bridge.forward(100)?;
+        bridge.ramp_coast(10, 20, &mut delay)?;
+
+        assert_eq!(bridge.state(), MotorState::Coast);
+        assert_eq!(delay.total(), Duration::from_millis(180));
+
+        in1.done();
+        in2.done();
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_reverse_smooth_ramps_down_through_zero_and_up() -> Result<(), MotorDriverError> {
+        let mut in1_transactions = vec![];
+        let mut in2_transactions = vec![];
+
+        for duty in [80, 60, 40, 20] {
+            in1_transactions.push(PwmPinTransaction::max_duty_cycle(100));
+            in1_transactions.push(PwmPinTransaction::set_duty_cycle(duty));
+            in2_transactions.push(PwmPinTransaction::set_duty_cycle(0));
+        }
+
+        in1_transactions.push(PwmPinTransaction::set_duty_cycle(0));
+        in2_transactions.push(PwmPinTransaction::set_duty_cycle(0));
+
+        for duty in [20, 40, 60, 80] {
+            in1_transactions.push(PwmPinTransaction::set_duty_cycle(0));
+            in2_transactions.push(PwmPinTransaction::max_duty_cycle(100));
+            in2_transactions.push(PwmPinTransaction::set_duty_cycle(duty));
+        }
+
+        let mut in1 = PwmPin::new(&in1_transactions);
+        let mut in2 = PwmPin::new(&in2_transactions);
+
+        let mut bridge = PwmBridge::new(in1.clone(), in2.clone(), 0);
+        let mut delay = MockDelay::new();
+
+        bridge.set_decay(Decay::Fast);
+
+        bridge.forward(80)?;
+        bridge.reverse_smooth(80, 4, 10, &mut delay)?;
+
+        assert_eq!(bridge.state(), MotorState::Reverse(80));
+        assert_eq!(delay.total(), Duration::from_millis(70));
+
+        in1.done();
+        in2.done();
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_drive_reports_the_raw_duty_written() -> Result<(), MotorDriverError> {
+        let mut in1 = PwmPin::new(&[
+            PwmPinTransaction::max_duty_cycle(1000),
+            PwmPinTransaction::set_duty_cycle(600),
+            PwmPinTransaction::max_duty_cycle(1000),
+            PwmPinTransaction::set_duty_cycle(1000),
+        ]);
+
+        let mut in2 = PwmPin::new(&[
+            PwmPinTransaction::set_duty_cycle(0),
+            PwmPinTransaction::max_duty_cycle(1000),
+            PwmPinTransaction::set_duty_cycle(400),
+        ]);
+
+        let mut bridge = PwmBridge::new(in1.clone(), in2.clone(), 200);
+
+        bridge.set_decay(Decay::Fast);
+
+        assert_eq!(bridge.drive_reporting_duty(Direction::Forward, 50)?, 600);
+
+        bridge.set_decay(Decay::Slow);
+
+        assert_eq!(bridge.drive_reporting_duty(Direction::Forward, 50)?, 400);
+
+        in1.done();
+        in2.done();
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_brake_policy_keeps_the_bridge_from_braking() -> Result<(), MotorDriverError> {
+        let mut in1 = Pin::new(&[Transaction::set(High), Transaction::set(Low)]);
+        let mut in2 = Pin::new(&[Transaction::set(Low), Transaction::set(Low)]);
+
+        let mut bridge = Bridge::new(in1.clone(), in2.clone());
+
+        bridge.set_brake_policy(BrakePolicy::Coast);
+        bridge.forward()?;
+        bridge.stop()?;
+
+        assert_eq!(bridge.state(), MotorState::Coast);
+
+        bridge.set_brake_policy(BrakePolicy::Reject);
+
+        assert_eq!(bridge.stop(), Err(MotorDriverError::Unsupported));
+        assert_eq!(bridge.brake_low_side(), Err(MotorDriverError::Unsupported));
+
+        in1.done();
+        in2.done();
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_state_can_be_replayed() -> Result<(), MotorDriverError> {
+        let mut in1 = PwmPin::new(&[
+            PwmPinTransaction::max_duty_cycle(100),
+            PwmPinTransaction::set_duty_cycle(40),
+            PwmPinTransaction::set_duty_cycle(0),
+            PwmPinTransaction::max_duty_cycle(100),
+            PwmPinTransaction::set_duty_cycle(40),
+        ]);
+
+        let mut in2 = PwmPin::new(&[
+            PwmPinTransaction::set_duty_cycle(0),
+            PwmPinTransaction::set_duty_cycle(0),
+            PwmPinTransaction::set_duty_cycle(0),
+        ]);
+
+        let mut bridge = PwmBridge::new(in1.clone(), in2.clone(), 0);
+
+        bridge.set_decay(Decay::Fast);
+        bridge.forward(40)?;
+
+        let snapshot = bridge.state();
+
+        bridge.coast()?;
+        snapshot.apply_to(&mut bridge)?;
+
+        assert_eq!(bridge.state(), MotorState::Forward(40));
+
+        in1.done();
+        in2.done();
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_set_direction_keeps_the_last_speed() -> Result<(), MotorDriverError> {
+        let mut in1 = PwmPin::new(&[
+            PwmPinTransaction::max_duty_cycle(100),
+            PwmPinTransaction::set_duty_cycle(40),
+            PwmPinTransaction::set_duty_cycle(0),
+        ]);
+
+        let mut in2 = PwmPin::new(&[
+            PwmPinTransaction::set_duty_cycle(0),
+            PwmPinTransaction::max_duty_cycle(100),
+            PwmPinTransaction::set_duty_cycle(40),
+        ]);
+
+        let mut bridge = PwmBridge::new(in1.clone(), in2.clone(), 0);
+
+        bridge.set_decay(Decay::Fast);
+
+        bridge.forward(40)?;
+        bridge.set_direction(Direction::Reverse)?;
+
+        assert_eq!(bridge.state(), MotorState::Reverse(40));
+
+        in1.done();
+        in2.done();
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_an_absent_optional_motor_ignores_commands() -> Result<(), MotorDriverError> {
+        let mut in1 = Pin::new(&[Transaction::set(High), Transaction::set(Low)]);
+        let mut in2 = Pin::new(&[Transaction::set(Low), Transaction::set(Low)]);
+
+        let mut present = Some(Bridge::new(in1.clone(), in2.clone()));
+        let mut absent = None::<Bridge<Pin, Pin>>;
+
+        present.forward()?;
+        present.coast()?;
+
+        absent.forward()?;
+        absent.stop()?;
+
+        in1.done();
+        in2.done();
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_duties_below_the_min_on_time_are_snapped() -> Result<(), MotorDriverError> {
+        let mut in1 = PwmPin::new(&[
+            PwmPinTransaction::max_duty_cycle(100),
+            PwmPinTransaction::set_duty_cycle(5),
+            PwmPinTransaction::max_duty_cycle(100),
+            PwmPinTransaction::set_duty_cycle(0),
+            PwmPinTransaction::max_duty_cycle(100),
+            PwmPinTransaction::set_duty_cycle(40),
+        ]);
+
+        let mut in2 = PwmPin::new(&[
+            PwmPinTransaction::set_duty_cycle(0),
+            PwmPinTransaction::set_duty_cycle(0),
+            PwmPinTransaction::set_duty_cycle(0),
+        ]);
+
+        let mut bridge = PwmBridge::new(in1.clone(), in2.clone(), 0);
+
+        bridge.set_decay(Decay::Fast);
+
+        assert!(matches!(bridge.set_min_on_time_ns(500, 0), Err(MotorDriverError::InvalidRange)));
+
+        bridge.set_min_on_time_ns(500, 10_000)?;
+
+        bridge.forward(3)?;
+        bridge.forward(2)?;
+        bridge.forward(40)?;
+
+        in1.done();
+        in2.done();
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_forward_for_us_coasts_after_the_requested_microseconds() -> Result<(), MotorDriverError> {
+        let mut in1 = PwmPin::new(&[
+            PwmPinTransaction::max_duty_cycle(100),
+            PwmPinTransaction::set_duty_cycle(50),
+            PwmPinTransaction::set_duty_cycle(0),
+        ]);
+
+        let mut in2 = PwmPin::new(&[PwmPinTransaction::set_duty_cycle(0), PwmPinTransaction::set_duty_cycle(0)]);
+
+        let mut bridge = PwmBridge::new(in1.clone(), in2.clone(), 0);
+        let mut delay = MockDelay::new();
+
+        bridge.set_decay(Decay::Fast);
+
+        bridge.forward_for_us(50, 500, &mut delay)?;
+
+        assert_eq!(delay.delays(), &[Duration::from_micros(500)]);
+        assert_eq!(bridge.state(), MotorState::Coast);
+
+        in1.done();
+        in2.done();
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_clamped_speed_reports_the_applied_percentage() -> Result<(), MotorDriverError> {
+        let mut in1 = PwmPin::new(&[PwmPinTransaction::max_duty_cycle(100), PwmPinTransaction::set_duty_cycle(100)]);
+        let mut in2 = PwmPin::new(&[PwmPinTransaction::set_duty_cycle(0)]);
+
+        let mut bridge = PwmBridge::new(in1.clone(), in2.clone(), 0);
+
+        bridge.set_decay(Decay::Fast);
+
+        assert_eq!(bridge.forward_clamped(150)?, 100);
+        assert_eq!(bridge.state(), MotorState::Forward(100));
+
+        in1.done();
+        in2.done();
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_rpm_is_estimated_from_the_commanded_duty() -> Result<(), MotorDriverError> {
+        let mut in1 = PwmPin::new(&[PwmPinTransaction::max_duty_cycle(100), PwmPinTransaction::set_duty_cycle(50)]);
+        let mut in2 = PwmPin::new(&[PwmPinTransaction::set_duty_cycle(0)]);
+
+        let mut bridge = PwmBridge::new(in1.clone(), in2.clone(), 0);
+
+        bridge.set_decay(Decay::Fast);
+        bridge.set_speed_constant(1000);
+
+        assert_eq!(bridge.estimate_rpm(7400), 0);
+
+        bridge.forward(50)?;
+
+        assert_eq!(bridge.estimate_rpm(7400), 3700);
+
+        in1.done();
+        in2.done();
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_coast_from_brake_passes_through_the_last_direction() -> Result<(), MotorDriverError> {
+        for (direction, first) in [(Direction::Forward, 2), (Direction::Reverse, 1)] {
+            let log = Rc::new(RefCell::new(Vec::new()));
+            let mut bridge = Bridge::new(LoggedPin(1, log.clone()), LoggedPin(2, log.clone()));
+
+            match direction {
+                Direction::Forward => bridge.forward()?,
+                Direction::Reverse => bridge.reverse()?,
+            }
+
+            bridge.stop()?;
+            log.borrow_mut().clear();
+            bridge.coast()?;
+
+            assert_eq!(*log.borrow(), [(first, PinState::Low), (3 - first, PinState::Low)]);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_move_ticks_brakes_on_target_or_timeout() -> Result<(), MotorDriverError> {
+        let mut in1 = PwmPin::new(&[
+            PwmPinTransaction::max_duty_cycle(100),
+            PwmPinTransaction::set_duty_cycle(50),
+            PwmPinTransaction::max_duty_cycle(100),
+            PwmPinTransaction::set_duty_cycle(100),
+            PwmPinTransaction::max_duty_cycle(100),
+            PwmPinTransaction::set_duty_cycle(50),
+            PwmPinTransaction::max_duty_cycle(100),
+            PwmPinTransaction::set_duty_cycle(100),
+        ]);
+
+        let mut in2 = PwmPin::new(&[
+            PwmPinTransaction::set_duty_cycle(0),
+            PwmPinTransaction::max_duty_cycle(100),
+            PwmPinTransaction::set_duty_cycle(100),
+            PwmPinTransaction::set_duty_cycle(0),
+            PwmPinTransaction::max_duty_cycle(100),
+            PwmPinTransaction::set_duty_cycle(100),
+        ]);
+
+        let mut bridge = PwmBridge::new(in1.clone(), in2.clone(), 0);
+        let mut delay = MockDelay::new();
+        let mut encoder = u32::MAX - 4;
+
+        let mut count = || {
+            encoder = encoder.wrapping_add(4);
+            encoder
+        };
+
+        bridge.set_decay(Decay::Fast);
+
+        let reached = bridge.move_ticks(Direction::Forward, 50, 10, &mut count, 100, &mut delay)?;
+
+        assert!(reached);
+        assert_eq!(delay.delays().len(), 2);
+        assert_eq!(bridge.state(), MotorState::Stop);
+
+        let reached = bridge.move_ticks(Direction::Forward, 50, 10, &mut || 0, 5, &mut delay)?;
+
+        assert!(!reached);
+        assert_eq!(delay.delays().len(), 7);
+        assert_eq!(bridge.state(), MotorState::Stop);
+
+        in1.done();
+        in2.done();
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_move_ticks_coasts_when_braking_is_rejected() {
+        let mut in1 = PwmPin::new(&[
+            PwmPinTransaction::max_duty_cycle(100),
+            PwmPinTransaction::set_duty_cycle(50),
+            PwmPinTransaction::set_duty_cycle(0),
+        ]);
+        let mut in2 = PwmPin::new(&[PwmPinTransaction::set_duty_cycle(0), PwmPinTransaction::set_duty_cycle(0)]);
+
+        let mut bridge = PwmBridge::new(in1.clone(), in2.clone(), 0);
+        let mut delay = MockDelay::new();
+
+        bridge.set_decay(Decay::Fast);
+        bridge.set_brake_policy(BrakePolicy::Reject);
+
+        let result = bridge.move_ticks(Direction::Forward, 50, 10, &mut || 10, 100, &mut delay);
+
+        assert_eq!(result, Err(MotorDriverError::Unsupported));
+        assert_eq!(bridge.state(), MotorState::Coast);
+
+        in1.done();
+        in2.done();
+    }
+
+    #[test]
+    fn test_torque_is_converted_to_duty_through_the_curve() -> Result<(), MotorDriverError> {
+        let mut in1 = PwmPin::new(&[PwmPinTransaction::max_duty_cycle(100), PwmPinTransaction::set_duty_cycle(55)]);
+        let mut in2 = PwmPin::new(&[PwmPinTransaction::set_duty_cycle(0)]);
+
+        let mut bridge = PwmBridge::new(in1.clone(), in2.clone(), 0);
+
+        bridge.set_decay(Decay::Fast);
+
+        assert_eq!(bridge.duty_for_torque(50), 50);
+        assert!(matches!(bridge.set_torque_curve([0, 10, 5, 15, 30, 45, 60, 72, 84, 93, 100]), Err(MotorDriverError::InvalidRange)));
+
+        bridge.set_torque_curve([0, 0, 5, 15, 30, 45, 55, 72, 84, 93, 100])?;
+
+        assert_eq!(bridge.duty_for_torque(0), 0);
+        assert_eq!(bridge.duty_for_torque(40), 47);
+        assert_eq!(bridge.duty_for_torque(100), 100);
+
+        bridge.drive_torque(50)?;
+
+        assert_eq!(bridge.state(), MotorState::Forward(55));
+
+        in1.done();
+        in2.done();
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_live_decay_update_redrives_at_the_same_speed() -> Result<(), MotorDriverError> {
+        let mut in1 = PwmPin::new(&[
+            PwmPinTransaction::max_duty_cycle(100),
+            PwmPinTransaction::set_duty_cycle(40),
+            PwmPinTransaction::max_duty_cycle(100),
+            PwmPinTransaction::set_duty_cycle(100),
+        ]);
+
+        let mut in2 = PwmPin::new(&[
+            PwmPinTransaction::set_duty_cycle(0),
+            PwmPinTransaction::max_duty_cycle(100),
+            PwmPinTransaction::set_duty_cycle(60),
+        ]);
+
+        let mut bridge = PwmBridge::new(in1.clone(), in2.clone(), 0);
+
+        bridge.set_decay(Decay::Fast);
+        bridge.forward(40)?;
+        bridge.update_decay_live(Decay::Slow)?;
+
+        assert_eq!(bridge.decay(), Decay::Slow);
+        assert_eq!(bridge.state(), MotorState::Forward(40));
+
+        in1.done();
+        in2.done();
+
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(all(feature = "panic-on-invalid", debug_assertions))]
+    #[should_panic(expected = "speed percentage 200 is above 100")]
+    fn test_out_of_range_percent_panics_with_panic_on_invalid() {
+        let in1 = PwmPin::new(&[]);
+        let in2 = PwmPin::new(&[]);
+
+        let mut bridge = PwmBridge::new(in1, in2, 0);
+
+        let _ = bridge.forward(200);
+    }
+
+    #[test]
+    #[cfg(not(all(feature = "panic-on-invalid", debug_assertions)))]
+    fn test_out_of_range_percent_is_rejected_without_writing() {
+        let mut in1 = PwmPin::new(&[]);
+        let mut in2 = PwmPin::new(&[]);
+
+        let mut bridge = PwmBridge::new(in1.clone(), in2.clone(), 0);
+
+        assert_eq!(bridge.forward(150), Err(MotorDriverError::InvalidRange));
+        assert_eq!(bridge.reverse(150), Err(MotorDriverError::InvalidRange));
+        assert_eq!(bridge.state(), MotorState::Coast);
+
+        in1.done();
+        in2.done();
+    }
+
+    #[test]
+    fn test_raw_levels_are_written_as_given() -> Result<(), MotorDriverError> {
+        let mut in1 = Pin::new(&[Transaction::set(High), Transaction::set(High)]);
+        let mut in2 = Pin::new(&[Transaction::set(Low), Transaction::set(High)]);
+
+        let mut bridge = Bridge::new(in1.clone(), in2.clone());
+
+        bridge.set_raw(PinState::High, PinState::Low)?;
+
+        assert_eq!(bridge.state(), MotorState::Forward(100));
+
+        bridge.set_raw(PinState::High, PinState::High)?;
+
+        assert_eq!(bridge.state(), MotorState::Stop);
+
+        in1.done();
+        in2.done();
+
+        Ok(())
     }
 }
@@ -0,0 +1,186 @@
+use embedded_hal::digital::OutputPin;
+use embedded_hal::pwm::SetDutyCycle;
+
+use crate::bridge::{RoundMode, TORQUE_CURVE_POINTS};
+use crate::config::DriverConfig;
+use crate::driver::{BrakePolicy, Decay, Direction, FaultPin, MotorDriver, MotorDriverError, PwmSplitDriverType};
+use crate::pwm_split_driver::PwmSplitDriver;
+
+/// Collects the tuning of a PWM split driver up front, so a single chain produces a fully configured
+/// driver instead of calling the setters one by one after [MotorDriver::new_pwm_split].
+///
+/// # Example
+///
+/// ```no_run
+/// use drv8833_driver::{Decay, MotorDriverBuilder};
+/// use embedded_hal_mock::eh1::pin::Mock as Pin;
+/// use embedded_hal_mock::eh1::pwm::Mock as PwmPin;
+///
+/// let (in1, in2, in3, in4) = (PwmPin::new(&[]), PwmPin::new(&[]), PwmPin::new(&[]), PwmPin::new(&[]));
+///
+/// let motor = MotorDriverBuilder::new()
+///     .min_duty(30)
+///     .decay(Decay::Slow)
+///     .build_pwm_split(in1, in2, in3, in4, None::<Pin>, None::<Pin>)?;
+/// # Ok::<(), drv8833_driver::MotorDriverError>(())
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MotorDriverBuilder {
+    config: DriverConfig,
+}
+
+impl MotorDriverBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Starts from existing settings, e.g. captured with [PwmBridge::config](crate::PwmBridge::config).
+    pub fn from_config(config: DriverConfig) -> Self {
+        Self { config }
+    }
+
+    /// See [PwmBridge::set_min_duty](crate::PwmBridge::set_min_duty).
+    pub fn min_duty(self, duty: u16) -> Self {
+        self.min_duty_directional(duty, duty)
+    }
+
+    /// See [PwmBridge::set_min_duty_directional](crate::PwmBridge::set_min_duty_directional).
+    pub fn min_duty_directional(mut self, forward: u16, reverse: u16) -> Self {
+        self.config.min_duty_forward = forward;
+        self.config.min_duty_reverse = reverse;
+        self
+    }
+
+    /// See [PwmBridge::set_max_duty](crate::PwmBridge::set_max_duty), checked when building.
+    pub fn max_duty(mut self, duty: u16) -> Self {
+        self.config.max_duty = Some(duty);
+        self
+    }
+
+    pub fn decay(mut self, decay: Decay) -> Self {
+        self.config.decay = decay;
+        self
+    }
+
+    pub fn deadband(mut self, deadband: u8) -> Self {
+        self.config.deadband = deadband;
+        self
+    }
+
+    /// See [PwmBridge::set_torque_curve](crate::PwmBridge::set_torque_curve), checked when building.
+    pub fn torque_curve(mut self, curve: [u16; TORQUE_CURVE_POINTS]) -> Self {
+        self.config.torque_curve = Some(curve);
+        self
+    }
+
+    /// The direction turning the motors clockwise, [Direction::Reverse] for motors wired the other way around.
+    pub fn clockwise(mut self, direction: Direction) -> Self {
+        self.config.clockwise = direction;
+        self
+    }
+
+    pub fn round_mode(mut self, round: RoundMode) -> Self {
+        self.config.round = round;
+        self
+    }
+
+    pub fn brake_policy(mut self, policy: BrakePolicy) -> Self {
+        self.config.brake_policy = policy;
+        self
+    }
+
+    pub fn config(&self) -> &DriverConfig {
+        &self.config
+    }
+
+    /// Creates a PWM split driver, see [MotorDriver::new_pwm_split], with these settings on both bridges.
+    ///
+    /// Returns [MotorDriverError::InvalidRange] if the maximum duty or the torque curve are invalid, see
+    /// [PwmBridge::apply_config](crate::PwmBridge::apply_config).
+    pub fn build_pwm_split<IN1, IN2, IN3, IN4, SLEEP, FAULT>(
+        self,
+        in1: IN1,
+        in2: IN2,
+        in3: IN3,
+        in4: IN4,
+        sleep: Option<SLEEP>,
+        fault: Option<FAULT>,
+    ) -> Result<PwmSplitDriverType<IN1, IN2, IN3, IN4, SLEEP, FAULT>, MotorDriverError>
+        where
+            IN1: SetDutyCycle,
+            IN2: SetDutyCycle,
+            IN3: SetDutyCycle,
+            IN4: SetDutyCycle,
+            SLEEP: OutputPin,
+            FAULT: FaultPin,
+    {
+        let mut driver = PwmSplitDriver::new(in1, in2, in3, in4);
+
+        driver.apply_config(&self.config)?;
+
+        Ok(MotorDriver::from_driver(driver, sleep, fault))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use embedded_hal_mock::eh1::pin::Mock as Pin;
+    use embedded_hal_mock::eh1::pwm::Mock as PwmPin;
+    use embedded_hal_mock::eh1::pwm::Transaction as PwmPinTransaction;
+
+    use crate::builder::MotorDriverBuilder;
+    use crate::driver::{Decay, MotorDriverError, PwmMovement};
+
+    #[test]
+    fn test_the_builder_configures_both_bridges() -> Result<(), MotorDriverError> {
+        let expectations = [
+            PwmPinTransaction::max_duty_cycle(100),
+            PwmPinTransaction::set_duty_cycle(100),
+        ];
+
+        let mut in1 = PwmPin::new(&expectations);
+        let mut in2 = PwmPin::new(&[
+            PwmPinTransaction::max_duty_cycle(100),
+            PwmPinTransaction::set_duty_cycle(35),
+        ]);
+        let mut in3 = PwmPin::new(&expectations);
+        let mut in4 = PwmPin::new(&[
+            PwmPinTransaction::max_duty_cycle(100),
+            PwmPinTransaction::set_duty_cycle(35),
+        ]);
+
+        let mut motor = MotorDriverBuilder::new()
+            .min_duty(30)
+            .decay(Decay::Slow)
+            .build_pwm_split(in1.clone(), in2.clone(), in3.clone(), in4.clone(), None::<Pin>, None::<Pin>)?;
+
+        assert_eq!(motor.a.min_duty(), 30);
+        assert_eq!(motor.b.decay(), Decay::Slow);
+
+        motor.a.forward(50)?;
+        motor.b.forward(50)?;
+
+        in1.done();
+        in2.done();
+        in3.done();
+        in4.done();
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_the_builder_rejects_an_invalid_max_duty() {
+        let (in1, in2, in3, in4) = (PwmPin::new(&[]), PwmPin::new(&[]), PwmPin::new(&[]), PwmPin::new(&[]));
+
+        let result = MotorDriverBuilder::new()
+            .min_duty(50)
+            .max_duty(40)
+            .build_pwm_split(in1.clone(), in2.clone(), in3.clone(), in4.clone(), None::<Pin>, None::<Pin>);
+
+        assert_eq!(result.err(), Some(MotorDriverError::InvalidRange));
+
+        for mut pin in [in1, in2, in3, in4] {
+            pin.done();
+        }
+    }
+}
@@ -0,0 +1,133 @@
+use core::time::Duration;
+
+use embedded_hal::delay::DelayNs;
+
+use crate::driver::{Breaks, MotorDriverError};
+
+/// A simple model of how long a motor keeps spinning after being coasted, assuming it slows down
+/// at a constant rate until it stops.
+///
+/// The deceleration is motor and load specific and should be calibrated by measuring how long
+/// the motor takes to stop from a known speed.
+#[derive(Debug, Clone, Copy)]
+pub struct CoastModel {
+    deceleration: u32,
+}
+
+impl CoastModel {
+    /// Creates a new model decelerating at the given rate, in RPM per second.
+    pub fn new(deceleration: u32) -> Self {
+        Self { deceleration }
+    }
+
+    /// Estimates how long the motor takes to stop when coasted at the given speed.
+    pub fn estimate_coast(&self, rpm: u32) -> Duration {
+        if self.deceleration == 0 {
+            return Duration::ZERO;
+        }
+
+        Duration::from_millis(rpm as u64 * 1000 / self.deceleration as u64)
+    }
+
+    /// Non-blocking check of whether a motor coasted at the given speed is estimated to have stopped
+    /// after `elapsed` time, for use from polling loops instead of [CoastModel::coast_and_wait].
+    pub fn is_settled(&self, rpm: u32, elapsed: Duration) -> bool {
+        elapsed >= self.estimate_coast(rpm)
+    }
+
+    /// Coasts the motor and blocks until it is estimated to have stopped.
+    #[must_use = "motor command result must be checked"]
+    pub fn coast_and_wait<MOTOR, DELAY>(&self, motor: &mut MOTOR, rpm: u32, delay: &mut DELAY) -> Result<(), MotorDriverError>
+        where
+            MOTOR: Breaks,
+            DELAY: DelayNs,
+    {
+        motor.coast()?;
+
+        delay.delay_ms(u32::try_from(self.estimate_coast(rpm).as_millis()).unwrap_or(u32::MAX));
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use embedded_hal::delay::DelayNs;
+    use embedded_hal_mock::eh1::delay::NoopDelay;
+    use embedded_hal_mock::eh1::pin::Mock as Pin;
+    use embedded_hal_mock::eh1::pin::State::Low;
+    use embedded_hal_mock::eh1::pin::Transaction;
+
+    use crate::coast::CoastModel;
+    use crate::driver::{MotorDriver, MotorDriverError};
+
+    /// Records the last delay requested in milliseconds.
+    struct RecordingDelay(u32);
+
+    impl DelayNs for RecordingDelay {
+        fn delay_ns(&mut self, _ns: u32) {}
+
+        fn delay_ms(&mut self, ms: u32) {
+            self.0 = ms;
+        }
+    }
+
+    #[test]
+    fn test_coast_time_scales_with_speed() {
+        let model = CoastModel::new(2000);
+
+        assert_eq!(model.estimate_coast(3000), Duration::from_millis(1500));
+        assert_eq!(model.estimate_coast(0), Duration::ZERO);
+
+        assert!(!model.is_settled(3000, Duration::from_millis(1499)));
+        assert!(model.is_settled(3000, Duration::from_millis(1500)));
+    }
+
+    #[test]
+    fn test_coast_and_wait_coasts_the_motor() -> Result<(), MotorDriverError> {
+        let mut in1 = Pin::new(&[Transaction::set(Low)]);
+        let mut in2 = Pin::new(&[Transaction::set(Low)]);
+        let mut in3 = Pin::new(&[]);
+        let mut in4 = Pin::new(&[]);
+
+        let mut motor = MotorDriver::new_split(
+            in1.clone(), in2.clone(), in3.clone(), in4.clone(), None::<Pin>, None::<Pin>,
+        );
+
+        CoastModel::new(2000).coast_and_wait(&mut motor.a, 3000, &mut NoopDelay::new())?;
+
+        in1.done();
+        in2.done();
+        in3.done();
+        in4.done();
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_coast_and_wait_saturates_delays_beyond_u32_millis() -> Result<(), MotorDriverError> {
+        let mut in1 = Pin::new(&[Transaction::set(Low)]);
+        let mut in2 = Pin::new(&[Transaction::set(Low)]);
+        let mut in3 = Pin::new(&[]);
+        let mut in4 = Pin::new(&[]);
+
+        let mut motor = MotorDriver::new_split(
+            in1.clone(), in2.clone(), in3.clone(), in4.clone(), None::<Pin>, None::<Pin>,
+        );
+
+        let mut delay = RecordingDelay(0);
+
+        CoastModel::new(1).coast_and_wait(&mut motor.a, u32::MAX, &mut delay)?;
+
+        assert_eq!(delay.0, u32::MAX);
+
+        in1.done();
+        in2.done();
+        in3.done();
+        in4.done();
+
+        Ok(())
+    }
+}
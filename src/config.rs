@@ -0,0 +1,145 @@
+use crate::bridge::{RoundMode, TORQUE_CURVE_POINTS};
+use crate::driver::{BrakePolicy, Decay, Direction};
+
+/// The settings of a [PwmBridge](crate::PwmBridge), to configure several identical motors the same way.
+///
+/// It can be captured from an already configured bridge with [PwmBridge::config](crate::PwmBridge::config)
+/// or built directly, and is applied with `apply_config` on the bridges and PWM split drivers.
+///
+/// # Example
+///
+/// ```no_run
+/// use drv8833_driver::{Decay, DriverConfig, MotorDriver};
+/// use embedded_hal_mock::eh1::pin::Mock as Pin;
+/// use embedded_hal_mock::eh1::pwm::Mock as PwmPin;
+///
+/// let mut drivers = [(); 2].map(|_| {
+///     let (in1, in2, in3, in4) = (PwmPin::new(&[]), PwmPin::new(&[]), PwmPin::new(&[]), PwmPin::new(&[]));
+///
+///     MotorDriver::new_pwm_split(in1, in2, in3, in4, None::<Pin>, None::<Pin>)
+/// });
+///
+/// let config = DriverConfig {
+///     decay: Decay::Slow,
+///     min_duty_forward: 200,
+///     min_duty_reverse: 200,
+///     ..DriverConfig::default()
+/// };
+///
+/// for driver in &mut drivers {
+///     driver.apply_config(&config)?;
+/// }
+/// # Ok::<(), drv8833_driver::MotorDriverError>(())
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DriverConfig {
+    pub decay: Decay,
+    pub min_duty_forward: u16,
+    pub min_duty_reverse: u16,
+    /// The duty applied at 100%, see [PwmBridge::set_max_duty](crate::PwmBridge::set_max_duty).
+    pub max_duty: Option<u16>,
+    pub deadband: u8,
+    /// See [PwmBridge::set_torque_curve](crate::PwmBridge::set_torque_curve).
+    pub torque_curve: Option<[u16; TORQUE_CURVE_POINTS]>,
+    /// The direction turning the motor clockwise, [Direction::Reverse] for motors wired the other way around.
+    pub clockwise: Direction,
+    /// See [PwmBridge::set_round_mode](crate::PwmBridge::set_round_mode).
+    pub round: RoundMode,
+    /// See [PwmBridge::set_brake_policy](crate::PwmBridge::set_brake_policy).
+    pub brake_policy: BrakePolicy,
+}
+
+impl Default for DriverConfig {
+    /// The settings of a newly created bridge without a minimum duty.
+    fn default() -> Self {
+        Self {
+            decay: Decay::default(),
+            min_duty_forward: 0,
+            min_duty_reverse: 0,
+            max_duty: None,
+            deadband: 0,
+            torque_curve: None,
+            clockwise: Direction::Forward,
+            round: RoundMode::Nearest,
+            brake_policy: BrakePolicy::Brake,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use embedded_hal_mock::eh1::pwm::Mock as PwmPin;
+    use embedded_hal_mock::eh1::pwm::Transaction as PwmPinTransaction;
+
+    use crate::bridge::PwmBridge;
+    use crate::config::DriverConfig;
+    use crate::driver::{Decay, Direction, MotorDriverError, PwmMovement};
+
+    #[test]
+    fn test_a_config_applies_identically_to_every_bridge() -> Result<(), MotorDriverError> {
+        let config = DriverConfig {
+            decay: Decay::Fast,
+            min_duty_forward: 20,
+            max_duty: Some(80),
+            clockwise: Direction::Reverse,
+            ..DriverConfig::default()
+        };
+
+        let mut pins = Vec::new();
+        let mut bridges = Vec::new();
+
+        for _ in 0..2 {
+            let in1 = PwmPin::new(&[PwmPinTransaction::max_duty_cycle(100), PwmPinTransaction::set_duty_cycle(80)]);
+            let in2 = PwmPin::new(&[PwmPinTransaction::set_duty_cycle(0)]);
+
+            bridges.push(PwmBridge::new(in1.clone(), in2.clone(), 0));
+            pins.push((in1, in2));
+        }
+
+        for bridge in &mut bridges {
+            bridge.apply_config(&config)?;
+        }
+
+        for bridge in &mut bridges {
+            assert_eq!(bridge.config(), config);
+
+            bridge.forward(100)?;
+        }
+
+        for (mut in1, mut in2) in pins {
+            in1.done();
+            in2.done();
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_an_invalid_config_is_rejected_without_applying_it() {
+        let mut in1 = PwmPin::new(&[]);
+        let mut in2 = PwmPin::new(&[]);
+
+        let mut bridge = PwmBridge::new(in1.clone(), in2.clone(), 0);
+
+        let config = DriverConfig {
+            decay: Decay::Fast,
+            min_duty_forward: 50,
+            max_duty: Some(40),
+            ..DriverConfig::default()
+        };
+
+        assert_eq!(bridge.apply_config(&config), Err(MotorDriverError::InvalidRange));
+
+        let config = DriverConfig {
+            max_duty: None,
+            torque_curve: Some([0, 20, 10, 30, 40, 50, 60, 70, 80, 90, 100]),
+            ..config
+        };
+
+        assert_eq!(bridge.apply_config(&config), Err(MotorDriverError::InvalidRange));
+        assert_eq!(bridge.config().min_duty_forward, 0);
+
+        in1.done();
+        in2.done();
+    }
+}
@@ -1,24 +1,63 @@
-use std::fmt::Debug;
-use std::ops::{Deref, DerefMut};
+use core::fmt::{Debug, Display, Formatter};
+#[cfg(feature = "no-fault")]
+use core::marker::PhantomData;
+use core::ops::{Deref, DerefMut};
+#[cfg(feature = "std")]
+use std::error::Error;
+#[cfg(feature = "std")]
 use std::sync::{Arc, Mutex};
 
-use embedded_hal::digital::{InputPin, OutputPin};
+use embedded_hal::delay::DelayNs;
+#[cfg(not(feature = "no-fault"))]
+use embedded_hal::digital::InputPin;
+use embedded_hal::digital::{OutputPin, PinState, StatefulOutputPin};
 use embedded_hal::pwm::SetDutyCycle;
 
+#[cfg(not(feature = "no-fault"))]
+use crate::fault_debouncer::FaultDebouncer;
+use crate::bridge::Bridge;
+use crate::mixed_driver::MixedDriver;
 use crate::parallel_driver::ParallelDriver;
-use crate::pwm_parallel_driver::PwmParallelDriver;
-use crate::pwm_split_driver::PwmSplitDriver;
+use crate::pwm_parallel_driver::{OwnedPwm, PwmParallelDriver};
+use crate::pwm_split_driver::{PwmChannelId, PwmSplitDriver};
 use crate::split_driver::SplitDriver;
+use crate::stepper::StepperDriver;
 
+#[cfg(feature = "std")]
 pub type PwmParallelDriverType<IN1, IN2, IN3, IN4, PWM, FAULT> = MotorDriver<PwmParallelDriver<IN1, IN2, IN3, IN4, Arc<Mutex<PWM>>>, Arc<Mutex<PWM>>, FAULT>;
+#[cfg(any(test, feature = "critical-section-pwm"))]
+pub type PwmParallelCriticalSectionDriverType<IN1, IN2, IN3, IN4, PWM, FAULT> =
+    MotorDriver<PwmParallelDriver<IN1, IN2, IN3, IN4, &'static critical_section::Mutex<core::cell::RefCell<PWM>>>, (), FAULT>;
+pub type PwmParallelSingleOwnerDriverType<IN1, IN2, IN3, IN4, PWM, FAULT> = MotorDriver<PwmParallelDriver<IN1, IN2, IN3, IN4, OwnedPwm<PWM>>, (), FAULT>;
 pub type PwmSplitDriverType<IN1, IN2, IN3, IN4, SLEEP, FAULT> = MotorDriver<PwmSplitDriver<IN1, IN2, IN3, IN4>, Option<SLEEP>, FAULT>;
 pub type PwmSplitSingleDriverType<IN1, IN2, IN3, IN4, PWM, FAULT> = MotorDriver<SplitDriver<IN1, IN2, IN3, IN4>, PWM, FAULT>;
+pub type StepperDriverType<IN1, IN2, IN3, IN4, SLEEP, FAULT> = MotorDriver<StepperDriver<IN1, IN2, IN3, IN4>, Option<SLEEP>, FAULT>;
 pub type SplitDriverType<IN1, IN2, IN3, IN4, SLEEP, FAULT> = MotorDriver<SplitDriver<IN1, IN2, IN3, IN4>, Option<SLEEP>, FAULT>;
+pub type MixedDriverType<IN1, IN2, IN3, IN4, SLEEP, FAULT> = MotorDriver<MixedDriver<IN1, IN2, IN3, IN4>, Option<SLEEP>, FAULT>;
+pub type SingleBridgeDriverType<IN1, IN2, SLEEP, FAULT> = MotorDriver<Bridge<IN1, IN2>, Option<SLEEP>, FAULT>;
 pub type ParallelDriverType<IN1, IN2, IN3, IN4, SLEEP, FAULT> = MotorDriver<ParallelDriver<IN1, IN2, IN3, IN4>, Option<SLEEP>, FAULT>;
 
 /// Generics trait implemented by all drive modes.
 pub trait Driver {}
 
+/// Bound of the fault pin, which must implement [InputPin].
+///
+/// With the `no-fault` feature the fault subsystem is compiled out: [MotorDriver] no longer stores the
+/// pin nor provides [is_faulty](MotorDriver::is_faulty), and any type is accepted, so `None::<()>` can be
+/// passed to the constructors, whose signatures are otherwise unchanged.
+#[cfg(not(feature = "no-fault"))]
+pub trait FaultPin: InputPin {}
+
+#[cfg(not(feature = "no-fault"))]
+impl<T: InputPin> FaultPin for T {}
+
+/// Bound of the fault pin, which accepts any type as the `no-fault` feature is enabled.
+#[cfg(feature = "no-fault")]
+pub trait FaultPin {}
+
+#[cfg(feature = "no-fault")]
+impl<T> FaultPin for T {}
+
 /// Represents a motor driver, providing access to various modes of operation.
 ///
 /// This struct facilitates the creation of four different modes:
@@ -28,13 +67,52 @@ pub trait Driver {}
 /// - [`MotorDriver::new_pwm_split`]: Allows individual control over each bridge using PWM signals.
 /// - [`MotorDriver::new_pwm_split_single`]: Allows individual control over each bridge while using a single PWM signal over the eep pin.
 /// - [`MotorDriver::new_pwm_parallel`]: Controls both bridges simultaneously with a single PWM signal.
-pub struct MotorDriver<DRIVER: Driver, SLEEP, FAULT: InputPin> {
+/// - [`MotorDriver::new_pwm_parallel_single_owner`]: Same as above, without sharing the PWM behind an `Arc<Mutex<_>>`.
+/// - [`MotorDriver::new_mixed`]: Controls bridge A with PWM signals and bridge B with digital pins.
+/// - [`MotorDriver::new_stepper`]: Drives a bipolar stepper motor, with one coil on each bridge.
+pub struct MotorDriver<DRIVER: Driver, SLEEP, FAULT: FaultPin> {
     driver: DRIVER,
     sleep: SLEEP,
+    #[cfg(not(feature = "no-fault"))]
     fault: Option<FAULT>,
+    #[cfg(not(feature = "no-fault"))]
+    fault_b: Option<FAULT>,
+    #[cfg(feature = "no-fault")]
+    fault: PhantomData<FAULT>,
+    sleep_behavior: SleepBehavior,
+    duty: u8,
+    awake: bool,
+    strict_sleep: bool,
+    snapshot: Option<[MotorState; 2]>,
+    stall_current_ma: u16,
+    wake_settle_us: u32,
+}
+
+/// Controls what happens to the IN pins when [MotorDriver::sleep] is called.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum SleepBehavior {
+    /// Only nSLEEP is driven low, the IN pins are left in their current state.
+    #[default]
+    ChipOnly,
+    /// Both bridges are coasted before nSLEEP is driven low, so the outputs are left
+    /// undriven even if the nSLEEP signal is missed or not wired.
+    CoastThenSleep,
+}
+
+/// Controls what [Breaks::stop] does, for mechanisms such as fragile geartrains which are damaged by
+/// active braking.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BrakePolicy {
+    /// Braking drives both inputs high.
+    #[default]
+    Brake,
+    /// Braking coasts the motor instead.
+    Coast,
+    /// Braking fails with [MotorDriverError::Unsupported] without touching the pins.
+    Reject,
 }
 
-impl<DRIVER: Driver, SLEEP, FAULT: InputPin> Deref for MotorDriver<DRIVER, SLEEP, FAULT> {
+impl<DRIVER: Driver, SLEEP, FAULT: FaultPin> Deref for MotorDriver<DRIVER, SLEEP, FAULT> {
     type Target = DRIVER;
 
     fn deref(&self) -> &Self::Target {
@@ -42,12 +120,96 @@ impl<DRIVER: Driver, SLEEP, FAULT: InputPin> Deref for MotorDriver<DRIVER, SLEEP
     }
 }
 
-impl<DRIVER: Driver, SLEEP, FAULT: InputPin> DerefMut for MotorDriver<DRIVER, SLEEP, FAULT> {
+impl<DRIVER: Driver, SLEEP, FAULT: FaultPin> DerefMut for MotorDriver<DRIVER, SLEEP, FAULT> {
     fn deref_mut(&mut self) -> &mut Self::Target {
         &mut self.driver
     }
 }
 
+impl<DRIVER: Driver, SLEEP, FAULT: FaultPin> MotorDriver<DRIVER, SLEEP, FAULT> {
+    /// Wraps an already built inner driver, e.g. a [SplitDriver] whose bridges were configured
+    /// beforehand, with the given sleep and fault pins.
+    ///
+    /// The `sleep` argument takes the same shape as in the mode specific constructor, e.g. an
+    /// `Option` of the nSLEEP pin for [SplitDriverType] or the PWM applied to eep for [PwmSplitSingleDriverType].
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use drv8833_driver::{Direction, MotorDriver, SplitDriver};
+    /// use embedded_hal_mock::eh1::pin::Mock as Pin;
+    ///
+    /// let (in1, in2, in3, in4) = (Pin::new(&[]), Pin::new(&[]), Pin::new(&[]), Pin::new(&[]));
+    /// let sleep = Pin::new(&[]);
+    ///
+    /// let mut split = SplitDriver::new(in1, in2, in3, in4);
+    ///
+    /// split.a.set_clockwise_direction(Direction::Reverse);
+    ///
+    /// let mut motor = MotorDriver::from_driver(split, Some(sleep), None::<Pin>);
+    ///
+    /// motor.a.clockwise()?;
+    /// # Ok::<(), drv8833_driver::MotorDriverError>(())
+    /// ```
+    #[cfg_attr(feature = "no-fault", allow(unused_variables))]
+    pub fn from_driver(driver: DRIVER, sleep: SLEEP, fault: Option<FAULT>) -> Self {
+        MotorDriver {
+            driver,
+            sleep,
+            #[cfg(not(feature = "no-fault"))]
+            fault,
+            #[cfg(not(feature = "no-fault"))]
+            fault_b: None,
+            #[cfg(feature = "no-fault")]
+            fault: PhantomData,
+            sleep_behavior: SleepBehavior::default(),
+            duty: 0,
+            awake: false,
+            strict_sleep: false,
+            snapshot: None,
+            stall_current_ma: 0,
+            wake_settle_us: 1000,
+        }
+    }
+
+    /// Returns the inner driver, the same as dereferencing but without relying on auto-deref to pick
+    /// between methods of the same name.
+    pub fn driver_ref(&self) -> &DRIVER {
+        &self.driver
+    }
+
+    /// Returns the inner driver mutably, see [MotorDriver::driver_ref].
+    pub fn driver_mut(&mut self) -> &mut DRIVER {
+        &mut self.driver
+    }
+
+    /// Consumes the motor and returns the owned inner driver, dropping the sleep and fault pins.
+    pub fn into_driver(self) -> DRIVER {
+        self.driver
+    }
+
+    /// Sets the stall current of a single motor in milliamps, as found in its datasheet, used by
+    /// [MotorDriver::estimated_current_ma].
+    pub fn set_stall_current_ma(&mut self, ma: u16) {
+        self.stall_current_ma = ma;
+    }
+}
+
+impl<DRIVER, SLEEP, FAULT> MotorDriver<DRIVER, SLEEP, FAULT>
+    where
+        DRIVER: Driver + CurrentLoad,
+        FAULT: FaultPin,
+{
+    /// Estimates the total current drawn from the supply in milliamps, from the [stall current](MotorDriver::set_stall_current_ma)
+    /// scaled by the duty commanded to each output, with paralleled outputs counting twice.
+    ///
+    /// This is a rough open-loop figure meant for power budgeting: it ignores the back-EMF of a spinning
+    /// motor, which lowers the actual current, and the duty of a PWM applied to the eep pin.
+    pub fn estimated_current_ma(&self) -> u32 {
+        self.stall_current_ma as u32 * self.driver.load_percent() as u32 / 100
+    }
+}
+
 impl<IN1, IN2, IN3, IN4, SLEEP, FAULT> SplitDriverType<IN1, IN2, IN3, IN4, SLEEP, FAULT>
     where
         IN1: OutputPin,
@@ -55,26 +217,28 @@ impl<IN1, IN2, IN3, IN4, SLEEP, FAULT> SplitDriverType<IN1, IN2, IN3, IN4, SLEEP
         IN3: OutputPin,
         IN4: OutputPin,
         SLEEP: OutputPin,
-        FAULT: InputPin,
+        FAULT: FaultPin,
 {
     /// Creates a new [MotorDriver] instance with split control mode.
     ///
     /// In split mode, each bridge of the motor driver can be controlled independently.
     ///
+    /// The IN pins and the optional sleep pin must implement [OutputPin].
+    ///
     /// # Example
     ///
-    /// ```ignore
-    /// use drv8833_driver::driver::MotorDriver;
+    /// ```no_run
+    /// use drv8833_driver::{MotorDriver, Movement};
+    /// use embedded_hal_mock::eh1::pin::Mock as Pin;
+    ///
+    /// let (in1, in2, in3, in4) = (Pin::new(&[]), Pin::new(&[]), Pin::new(&[]), Pin::new(&[]));
+    /// let sleep = Pin::new(&[]);
     ///
-    /// let in1 = PinDriver::output(peripherals.pins.gpio5)?;
-    /// let in2 = PinDriver::output(peripherals.pins.gpio4)?;
-    /// let in3 = PinDriver::output(peripherals.pins.gpio18)?;
-    /// let in4 = PinDriver::output(peripherals.pins.gpio19)?;
-    /// let sleep = PinDriver::output(peripherals.pins.gpio3)?;
+    /// let mut motor = MotorDriver::new_split(in1, in2, in3, in4, Some(sleep), None::<Pin>);
     ///
-    /// let mut motor = MotorDriver::new_split(
-    ///     in1, in2, in3, in4, Some(sleep), None::<PinDriver<AnyInputPin, Input>>,
-    /// );
+    /// motor.wakeup()?;
+    /// motor.a.forward()?;
+    /// # Ok::<(), drv8833_driver::MotorDriverError>(())
     /// ```
     pub fn new_split(
         in1: IN1,
@@ -83,18 +247,54 @@ impl<IN1, IN2, IN3, IN4, SLEEP, FAULT> SplitDriverType<IN1, IN2, IN3, IN4, SLEEP
         in4: IN4,
         sleep: Option<SLEEP>,
         fault: Option<FAULT>,
-    ) -> SplitDriverType<IN1, IN2, IN3, IN4, SLEEP, FAULT>
-        where
-            IN1: OutputPin,
-            IN2: OutputPin,
-            IN3: OutputPin,
-            IN4: OutputPin,
-    {
-        MotorDriver {
-            driver: SplitDriver::new(in1, in2, in3, in4),
-            sleep,
-            fault,
-        }
+    ) -> SplitDriverType<IN1, IN2, IN3, IN4, SLEEP, FAULT> {
+        MotorDriver::from_driver(SplitDriver::new(in1, in2, in3, in4), sleep, fault)
+    }
+
+    /// Like [MotorDriver::new_split], but first drives every IN pin low so the bridges start coasting.
+    ///
+    /// All four pins are written even if some fail, and the returned [PinInitError] lists each one
+    /// which did, e.g. to report every bad connection at once.
+    pub fn try_new_split(
+        mut in1: IN1,
+        mut in2: IN2,
+        mut in3: IN3,
+        mut in4: IN4,
+        sleep: Option<SLEEP>,
+        fault: Option<FAULT>,
+    ) -> Result<SplitDriverType<IN1, IN2, IN3, IN4, SLEEP, FAULT>, PinInitError> {
+        coast_pins(&mut in1, &mut in2, &mut in3, &mut in4)?;
+
+        Ok(Self::new_split(in1, in2, in3, in4, sleep, fault))
+    }
+
+    /// Gives up bridge B, returning a driver for the single motor on bridge A along with the freed IN3 and
+    /// IN4 pins, e.g. to use them as unrelated GPIO.
+    ///
+    /// The sleep pin and settings are kept, while the fault pin of bridge B, if any, and the states recorded
+    /// by [MotorDriver::deep_sleep] are dropped. The freed pins are returned as they were last driven.
+    pub fn take_bridge_b(self) -> (SingleBridgeDriverType<IN1, IN2, SLEEP, FAULT>, IN3, IN4) {
+        let (in3, in4) = self.driver.b.release();
+
+        let motor = MotorDriver {
+            driver: self.driver.a,
+            sleep: self.sleep,
+            #[cfg(not(feature = "no-fault"))]
+            fault: self.fault,
+            #[cfg(not(feature = "no-fault"))]
+            fault_b: None,
+            #[cfg(feature = "no-fault")]
+            fault: PhantomData,
+            sleep_behavior: self.sleep_behavior,
+            duty: self.duty,
+            awake: self.awake,
+            strict_sleep: self.strict_sleep,
+            snapshot: None,
+            stall_current_ma: self.stall_current_ma,
+            wake_settle_us: self.wake_settle_us,
+        };
+
+        (motor, in3, in4)
     }
 }
 
@@ -105,7 +305,7 @@ impl<IN1, IN2, IN3, IN4, SLEEP, FAULT> PwmSplitDriverType<IN1, IN2, IN3, IN4, SL
         IN3: SetDutyCycle,
         IN4: SetDutyCycle,
         SLEEP: OutputPin,
-        FAULT: InputPin,
+        FAULT: FaultPin,
 {
     /// Creates a new [MotorDriver] instance in PWM split control mode.
     ///
@@ -114,22 +314,23 @@ impl<IN1, IN2, IN3, IN4, SLEEP, FAULT> PwmSplitDriverType<IN1, IN2, IN3, IN4, SL
     /// device has limited PWM channels available, you may prefer using [`MotorDriver::new_pwm_parallel`],
     /// which consumes fewer PWM channels but sacrifices individual control over both bridges.
     ///
+    /// The IN pins must implement [SetDutyCycle] only, while the optional sleep pin must implement [OutputPin].
+    ///
     /// # Example:
     ///
-    /// ```ignore
-    /// use drv8833_driver::driver::MotorDriver;
+    /// ```no_run
+    /// use drv8833_driver::{MotorDriver, PwmMovement};
+    /// use embedded_hal_mock::eh1::pin::Mock as Pin;
+    /// use embedded_hal_mock::eh1::pwm::Mock as PwmPin;
     ///
-    /// let sleep = PinDriver::output(peripherals.pins.gpio3)?;
-    /// let timer = LedcTimerDriver::new(peripherals.ledc.timer0, &TimerConfig::default())?;
+    /// let (in1, in2, in3, in4) = (PwmPin::new(&[]), PwmPin::new(&[]), PwmPin::new(&[]), PwmPin::new(&[]));
+    /// let sleep = Pin::new(&[]);
     ///
-    /// let in1 = LedcDriver::new(peripherals.ledc.channel0, &timer, peripherals.pins.gpio5)?;
-    /// let in2 = LedcDriver::new(peripherals.ledc.channel1, &timer, peripherals.pins.gpio4)?;
-    /// let in3 = LedcDriver::new(peripherals.ledc.channel2, &timer, peripherals.pins.gpio18)?;
-    /// let in4 = LedcDriver::new(peripherals.ledc.channel3, &timer, peripherals.pins.gpio19)?;
+    /// let mut motor = MotorDriver::new_pwm_split(in1, in2, in3, in4, Some(sleep), None::<Pin>);
     ///
-    /// let mut motor = MotorDriver::new_pwm_split(
-    ///     in1, in2, in3, in4, Some(sleep), None::<PinDriver<AnyInputPin, Input>>,
-    /// );
+    /// motor.wakeup()?;
+    /// motor.a.forward(50)?;
+    /// # Ok::<(), drv8833_driver::MotorDriverError>(())
     /// ```
     pub fn new_pwm_split(
         in1: IN1,
@@ -139,11 +340,26 @@ impl<IN1, IN2, IN3, IN4, SLEEP, FAULT> PwmSplitDriverType<IN1, IN2, IN3, IN4, SL
         sleep: Option<SLEEP>,
         fault: Option<FAULT>,
     ) -> PwmSplitDriverType<IN1, IN2, IN3, IN4, SLEEP, FAULT> {
-        MotorDriver {
-            driver: PwmSplitDriver::new(in1, in2, in3, in4),
-            sleep,
-            fault,
-        }
+        MotorDriver::from_driver(PwmSplitDriver::new(in1, in2, in3, in4), sleep, fault)
+    }
+
+    /// Same as [MotorDriver::new_pwm_split], but in debug builds asserts that no PWM channel is passed to
+    /// more than one IN pin, for HALs whose channel handles implement [PwmChannelId].
+    pub fn new_pwm_split_checked(
+        in1: IN1,
+        in2: IN2,
+        in3: IN3,
+        in4: IN4,
+        sleep: Option<SLEEP>,
+        fault: Option<FAULT>,
+    ) -> PwmSplitDriverType<IN1, IN2, IN3, IN4, SLEEP, FAULT>
+        where
+            IN1: PwmChannelId,
+            IN2: PwmChannelId,
+            IN3: PwmChannelId,
+            IN4: PwmChannelId,
+    {
+        MotorDriver::from_driver(PwmSplitDriver::new_checked(in1, in2, in3, in4), sleep, fault)
     }
 }
 
@@ -154,7 +370,7 @@ impl<IN1, IN2, IN3, IN4, SLEEP, FAULT> ParallelDriverType<IN1, IN2, IN3, IN4, SL
         IN3: OutputPin,
         IN4: OutputPin,
         SLEEP: OutputPin,
-        FAULT: InputPin,
+        FAULT: FaultPin,
 {
     /// Creates a new instance of `MotorDriver` in parallel control mode.
     ///
@@ -162,20 +378,22 @@ impl<IN1, IN2, IN3, IN4, SLEEP, FAULT> ParallelDriverType<IN1, IN2, IN3, IN4, SL
     /// The primary advantage of this mode is that it effectively doubles the current output when the bridges
     /// are physically connected in parallel, with IN1 connected to IN3 and IN2 connected to IN4.
     ///
+    /// The IN pins and the optional sleep pin must implement [OutputPin].
+    ///
     /// # Example:
     ///
-    /// ```ignore
-    /// use drv8833_driver::driver::MotorDriver;
+    /// ```no_run
+    /// use drv8833_driver::{MotorDriver, Movement};
+    /// use embedded_hal_mock::eh1::pin::Mock as Pin;
+    ///
+    /// let (in1, in2, in3, in4) = (Pin::new(&[]), Pin::new(&[]), Pin::new(&[]), Pin::new(&[]));
+    /// let sleep = Pin::new(&[]);
     ///
-    /// let in1 = PinDriver::output(peripherals.pins.gpio5)?;
-    /// let in2 = PinDriver::output(peripherals.pins.gpio4)?;
-    /// let in3 = PinDriver::output(peripherals.pins.gpio18)?;
-    /// let in4 = PinDriver::output(peripherals.pins.gpio19)?;
-    /// let sleep = PinDriver::output(peripherals.pins.gpio3)?;
+    /// let mut motor = MotorDriver::new_parallel(in1, in2, in3, in4, Some(sleep), None::<Pin>);
     ///
-    /// let mut motor = MotorDriver::new_parallel(
-    ///     in1, in2, in3, in4, Some(sleep), None::<PinDriver<AnyInputPin, Input>>,
-    /// );
+    /// motor.wakeup()?;
+    /// motor.forward()?;
+    /// # Ok::<(), drv8833_driver::MotorDriverError>(())
     /// ```
     pub fn new_parallel(
         in1: IN1,
@@ -184,51 +402,62 @@ impl<IN1, IN2, IN3, IN4, SLEEP, FAULT> ParallelDriverType<IN1, IN2, IN3, IN4, SL
         in4: IN4,
         sleep: Option<SLEEP>,
         fault: Option<FAULT>,
-    ) -> ParallelDriverType<IN1, IN2, IN3, IN4, SLEEP, FAULT>
-        where
-            IN1: OutputPin,
-            IN2: OutputPin,
-            IN3: OutputPin,
-            IN4: OutputPin,
-    {
-        MotorDriver {
-            driver: ParallelDriver::new(in1, in2, in3, in4),
-            sleep,
-            fault,
-        }
+    ) -> ParallelDriverType<IN1, IN2, IN3, IN4, SLEEP, FAULT> {
+        MotorDriver::from_driver(ParallelDriver::new(in1, in2, in3, in4), sleep, fault)
+    }
+
+    /// Like [MotorDriver::new_parallel], but first drives every IN pin low so the bridges start coasting.
+    ///
+    /// All four pins are written even if some fail, and the returned [PinInitError] lists each one
+    /// which did, e.g. to report every bad connection at once.
+    pub fn try_new_parallel(
+        mut in1: IN1,
+        mut in2: IN2,
+        mut in3: IN3,
+        mut in4: IN4,
+        sleep: Option<SLEEP>,
+        fault: Option<FAULT>,
+    ) -> Result<ParallelDriverType<IN1, IN2, IN3, IN4, SLEEP, FAULT>, PinInitError> {
+        coast_pins(&mut in1, &mut in2, &mut in3, &mut in4)?;
+
+        Ok(Self::new_parallel(in1, in2, in3, in4, sleep, fault))
     }
 }
 
+#[cfg(feature = "std")]
 impl<IN1, IN2, IN3, IN4, PWM, FAULT> PwmParallelDriverType<IN1, IN2, IN3, IN4, PWM, FAULT>
     where
         IN1: OutputPin,
         IN2: OutputPin,
         IN3: OutputPin,
         IN4: OutputPin,
-        FAULT: InputPin,
+        PWM: SetDutyCycle,
+        FAULT: FaultPin,
 {
     /// Creates a new [MotorDriver] instance with PWM parallel control mode.
     ///
     /// In PWM parallel mode, a single PWM channel is used to control all four bridge inputs.
     /// This allows simultaneous control of all inputs. It's useful for scenarios where a single
-    /// motor needs increased current, achieved by connecting IN1 with IN3 and IN2 with IN4.
+    /// motor needs increased current, achieved by connecting IN1 with IN3 and IN2 with IN4. The duty
+    /// cycle is set and read back through the inner driver with `motor.set_duty_cycle(percent)` and
+    /// `motor.duty_cycle_percent()`, which keep in step with the movement commands.
+    ///
+    /// The IN pins must implement [OutputPin], while the PWM connected to the eep pin must implement [SetDutyCycle].
     ///
     /// # Example
     ///
-    /// ```ignore
-    /// use drv8833_driver::driver::Motor;
+    /// ```no_run
+    /// use drv8833_driver::{MotorDriver, PwmMovement};
+    /// use embedded_hal_mock::eh1::pin::Mock as Pin;
+    /// use embedded_hal_mock::eh1::pwm::Mock as PwmPin;
     ///
-    /// let in1 = PinDriver::output(peripherals.pins.gpio5)?;
-    /// let in2 = PinDriver::output(peripherals.pins.gpio4)?;
-    /// let in3 = PinDriver::output(peripherals.pins.gpio18)?;
-    /// let in4 = PinDriver::output(peripherals.pins.gpio19)?;
+    /// let (in1, in2, in3, in4) = (Pin::new(&[]), Pin::new(&[]), Pin::new(&[]), Pin::new(&[]));
+    /// let pwm = PwmPin::new(&[]);
     ///
-    /// let timer = LedcTimerDriver::new(peripherals.ledc.timer3, &TimerConfig::default())?;
-    /// let sleep = LedcDriver::new(peripherals.ledc.channel0, &timer, peripherals.pins.gpio3)?;
+    /// let mut motor = MotorDriver::new_pwm_parallel(in1, in2, in3, in4, pwm, None::<Pin>);
     ///
-    /// let mut motor = Motor::new_pwm_parallel(
-    ///     in1, in2, in3, in4, sleep, None::<PinDriver<AnyInputPin, Input>>,
-    /// );
+    /// motor.forward(50)?;
+    /// # Ok::<(), drv8833_driver::MotorDriverError>(())
     /// ```
     pub fn new_pwm_parallel(
         in1: IN1,
@@ -237,20 +466,109 @@ impl<IN1, IN2, IN3, IN4, PWM, FAULT> PwmParallelDriverType<IN1, IN2, IN3, IN4, P
         in4: IN4,
         pwm: PWM,
         fault: Option<FAULT>,
-    ) -> PwmParallelDriverType<IN1, IN2, IN3, IN4, PWM, FAULT>
-        where
-            IN1: OutputPin,
-            IN2: OutputPin,
-            IN3: OutputPin,
-            IN4: OutputPin,
-    {
+    ) -> PwmParallelDriverType<IN1, IN2, IN3, IN4, PWM, FAULT> {
         let pwm = Arc::new(Mutex::new(pwm));
 
-        MotorDriver {
-            driver: PwmParallelDriver::new(in1, in2, in3, in4, pwm.clone()),
-            sleep: pwm,
-            fault,
-        }
+        MotorDriver::from_driver(PwmParallelDriver::new(in1, in2, in3, in4, pwm.clone()), pwm, fault)
+    }
+}
+
+impl<IN1, IN2, IN3, IN4, PWM, FAULT> PwmParallelSingleOwnerDriverType<IN1, IN2, IN3, IN4, PWM, FAULT>
+    where
+        IN1: OutputPin,
+        IN2: OutputPin,
+        IN3: OutputPin,
+        IN4: OutputPin,
+        PWM: SetDutyCycle,
+        FAULT: FaultPin,
+{
+    /// Creates a new [MotorDriver] instance with PWM parallel control mode, where the PWM is owned
+    /// directly by the inner driver instead of being shared behind an `Arc<Mutex<_>>`.
+    ///
+    /// This behaves exactly like [MotorDriver::new_pwm_parallel] but requires no locking, which makes
+    /// it a better fit for single-threaded applications. The duty cycle is set through the inner
+    /// driver with `motor.set_duty_cycle(percent)`.
+    ///
+    /// The IN pins must implement [OutputPin], while the PWM connected to the eep pin must implement [SetDutyCycle].
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use drv8833_driver::{MotorDriver, PwmMovement};
+    /// use embedded_hal_mock::eh1::pin::Mock as Pin;
+    /// use embedded_hal_mock::eh1::pwm::Mock as PwmPin;
+    ///
+    /// let (in1, in2, in3, in4) = (Pin::new(&[]), Pin::new(&[]), Pin::new(&[]), Pin::new(&[]));
+    /// let pwm = PwmPin::new(&[]);
+    ///
+    /// let mut motor = MotorDriver::new_pwm_parallel_single_owner(in1, in2, in3, in4, pwm, None::<Pin>);
+    ///
+    /// motor.forward(50)?;
+    /// # Ok::<(), drv8833_driver::MotorDriverError>(())
+    /// ```
+    pub fn new_pwm_parallel_single_owner(
+        in1: IN1,
+        in2: IN2,
+        in3: IN3,
+        in4: IN4,
+        pwm: PWM,
+        fault: Option<FAULT>,
+    ) -> PwmParallelSingleOwnerDriverType<IN1, IN2, IN3, IN4, PWM, FAULT> {
+        MotorDriver::from_driver(PwmParallelDriver::new(in1, in2, in3, in4, OwnedPwm(pwm)), (), fault)
+    }
+}
+
+#[cfg(any(test, feature = "critical-section-pwm"))]
+impl<IN1, IN2, IN3, IN4, PWM, FAULT> PwmParallelCriticalSectionDriverType<IN1, IN2, IN3, IN4, PWM, FAULT>
+    where
+        IN1: OutputPin,
+        IN2: OutputPin,
+        IN3: OutputPin,
+        IN4: OutputPin,
+        PWM: SetDutyCycle,
+        FAULT: FaultPin,
+{
+    /// Creates a new [MotorDriver] instance with PWM parallel control mode, where the PWM is shared
+    /// through a static `critical_section::Mutex<RefCell<_>>` instead of an `Arc<Mutex<_>>`.
+    ///
+    /// Available with the `critical-section-pwm` feature. Every PWM access runs inside a critical
+    /// section, so the PWM can safely be shared with interrupt handlers on bare metal. The duty cycle is
+    /// set through the inner driver with `motor.set_duty_cycle(percent)`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # #[cfg(feature = "critical-section-pwm")]
+    /// # fn main() -> Result<(), drv8833_driver::MotorDriverError> {
+    /// use core::cell::RefCell;
+    ///
+    /// use critical_section::Mutex;
+    /// use drv8833_driver::{MotorDriver, PwmMovement};
+    /// use embedded_hal_mock::eh1::pin::Mock as Pin;
+    /// use embedded_hal_mock::eh1::pwm::Mock as PwmPin;
+    ///
+    /// let (in1, in2, in3, in4) = (Pin::new(&[]), Pin::new(&[]), Pin::new(&[]), Pin::new(&[]));
+    ///
+    /// // On bare metal this is usually a `static`, e.g. initialized through a `StaticCell`.
+    /// let pwm: &'static Mutex<RefCell<PwmPin>> = Box::leak(Box::new(Mutex::new(RefCell::new(PwmPin::new(&[])))));
+    ///
+    /// let mut motor = MotorDriver::new_pwm_parallel_critical_section(in1, in2, in3, in4, pwm, None::<Pin>);
+    ///
+    /// motor.forward(50)?;
+    /// # Ok(())
+    /// # }
+    /// # #[cfg(not(feature = "critical-section-pwm"))]
+    /// # fn main() {}
+    /// ```
+    pub fn new_pwm_parallel_critical_section(
+        in1: IN1,
+        in2: IN2,
+        in3: IN3,
+        in4: IN4,
+        pwm: &'static critical_section::Mutex<core::cell::RefCell<PWM>>,
+        fault: Option<FAULT>,
+    ) -> PwmParallelCriticalSectionDriverType<IN1, IN2, IN3, IN4, PWM, FAULT> {
+        MotorDriver::from_driver(PwmParallelDriver::new(in1, in2, in3, in4, pwm), (), fault)
     }
 }
 
@@ -260,29 +578,31 @@ impl<IN1, IN2, IN3, IN4, PWM, FAULT> PwmSplitSingleDriverType<IN1, IN2, IN3, IN4
         IN2: OutputPin,
         IN3: OutputPin,
         IN4: OutputPin,
-        FAULT: InputPin,
+        PWM: SetDutyCycle,
+        FAULT: FaultPin,
 {
     /// Creates a new [MotorDriver] instance with PWM split single control mode.
     ///
     /// In this mode, the PWM signal is applied to the eep PIN instead of each IN pin, thus
     /// conserving PWM channels.
     ///
+    /// The IN pins must implement [OutputPin], while the PWM connected to the eep pin must implement [SetDutyCycle].
+    ///
     /// # Example
     ///
-    /// ```ignore
-    /// use drv8833_driver::driver::Motor;
+    /// ```no_run
+    /// use drv8833_driver::{MotorDriver, Movement};
+    /// use embedded_hal_mock::eh1::pin::Mock as Pin;
+    /// use embedded_hal_mock::eh1::pwm::Mock as PwmPin;
     ///
-    /// let in1 = PinDriver::output(peripherals.pins.gpio5)?;
-    /// let in2 = PinDriver::output(peripherals.pins.gpio4)?;
-    /// let in3 = PinDriver::output(peripherals.pins.gpio18)?;
-    /// let in4 = PinDriver::output(peripherals.pins.gpio19)?;
+    /// let (in1, in2, in3, in4) = (Pin::new(&[]), Pin::new(&[]), Pin::new(&[]), Pin::new(&[]));
+    /// let pwm = PwmPin::new(&[]);
     ///
-    /// let timer = LedcTimerDriver::new(peripherals.ledc.timer1, &TimerConfig::default())?;
-    /// let pwm = LedcDriver::new(peripherals.ledc.channel1, &timer, peripherals.pins.gpio3)?;
+    /// let mut motor = MotorDriver::new_pwm_split_single(in1, in2, in3, in4, pwm, None::<Pin>);
     ///
-    /// let mut motor = Motor::new_pwm_split_single(
-    ///     in1, in2, in3, in4, pwm, None::<PinDriver<AnyInputPin, Input>>,
-    /// );
+    /// motor.set_duty_cycle(50)?;
+    /// motor.a.forward()?;
+    /// # Ok::<(), drv8833_driver::MotorDriverError>(())
     /// ```
     pub fn new_pwm_split_single(
         in1: IN1,
@@ -291,18 +611,94 @@ impl<IN1, IN2, IN3, IN4, PWM, FAULT> PwmSplitSingleDriverType<IN1, IN2, IN3, IN4
         in4: IN4,
         pwm: PWM,
         fault: Option<FAULT>,
-    ) -> PwmSplitSingleDriverType<IN1, IN2, IN3, IN4, PWM, FAULT>
-        where
-            IN1: OutputPin,
-            IN2: OutputPin,
-            IN3: OutputPin,
-            IN4: OutputPin,
-    {
-        MotorDriver {
-            driver: SplitDriver::new(in1, in2, in3, in4),
-            sleep: pwm,
-            fault,
-        }
+    ) -> PwmSplitSingleDriverType<IN1, IN2, IN3, IN4, PWM, FAULT> {
+        MotorDriver::from_driver(SplitDriver::new(in1, in2, in3, in4), pwm, fault)
+    }
+}
+
+impl<IN1, IN2, IN3, IN4, SLEEP, FAULT> MixedDriverType<IN1, IN2, IN3, IN4, SLEEP, FAULT>
+    where
+        IN1: SetDutyCycle,
+        IN2: SetDutyCycle,
+        IN3: OutputPin,
+        IN4: OutputPin,
+        SLEEP: OutputPin,
+        FAULT: FaultPin,
+{
+    /// Creates a new [MotorDriver] instance in mixed control mode.
+    ///
+    /// In this mode, bridge A is speed controlled via PWM signals while bridge B is driven on/off by
+    /// digital pins, for devices short of the four PWM channels required by [`MotorDriver::new_pwm_split`].
+    /// Wire the motor needing speed control to bridge A.
+    ///
+    /// IN1 and IN2 must implement [SetDutyCycle], while IN3, IN4 and the optional sleep pin must implement [OutputPin].
+    ///
+    /// # Example:
+    ///
+    /// ```no_run
+    /// use drv8833_driver::{MotorDriver, Movement, PwmMovement};
+    /// use embedded_hal_mock::eh1::pin::Mock as Pin;
+    /// use embedded_hal_mock::eh1::pwm::Mock as PwmPin;
+    ///
+    /// let (in1, in2) = (PwmPin::new(&[]), PwmPin::new(&[]));
+    /// let (in3, in4) = (Pin::new(&[]), Pin::new(&[]));
+    ///
+    /// let mut motor = MotorDriver::new_mixed(in1, in2, in3, in4, None::<Pin>, None::<Pin>);
+    ///
+    /// motor.a.forward(50)?;
+    /// motor.b.forward()?;
+    /// # Ok::<(), drv8833_driver::MotorDriverError>(())
+    /// ```
+    pub fn new_mixed(
+        in1: IN1,
+        in2: IN2,
+        in3: IN3,
+        in4: IN4,
+        sleep: Option<SLEEP>,
+        fault: Option<FAULT>,
+    ) -> MixedDriverType<IN1, IN2, IN3, IN4, SLEEP, FAULT> {
+        MotorDriver::from_driver(MixedDriver::new(in1, in2, in3, in4), sleep, fault)
+    }
+}
+
+impl<IN1, IN2, IN3, IN4, SLEEP, FAULT> StepperDriverType<IN1, IN2, IN3, IN4, SLEEP, FAULT>
+    where
+        IN1: OutputPin,
+        IN2: OutputPin,
+        IN3: OutputPin,
+        IN4: OutputPin,
+        SLEEP: OutputPin,
+        FAULT: FaultPin,
+{
+    /// Creates a new [MotorDriver] instance in stepper mode.
+    ///
+    /// In this mode, both bridges drive the two coils of a bipolar stepper motor in full steps, coil A
+    /// on OUT1/OUT2 and coil B on OUT3/OUT4.
+    ///
+    /// The IN pins and the optional sleep pin must implement [OutputPin].
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use drv8833_driver::MotorDriver;
+    /// use embedded_hal_mock::eh1::pin::Mock as Pin;
+    ///
+    /// let (in1, in2, in3, in4) = (Pin::new(&[]), Pin::new(&[]), Pin::new(&[]), Pin::new(&[]));
+    ///
+    /// let mut motor = MotorDriver::new_stepper(in1, in2, in3, in4, None::<Pin>, None::<Pin>);
+    ///
+    /// motor.step_forward()?;
+    /// # Ok::<(), drv8833_driver::MotorDriverError>(())
+    /// ```
+    pub fn new_stepper(
+        in1: IN1,
+        in2: IN2,
+        in3: IN3,
+        in4: IN4,
+        sleep: Option<SLEEP>,
+        fault: Option<FAULT>,
+    ) -> StepperDriverType<IN1, IN2, IN3, IN4, SLEEP, FAULT> {
+        MotorDriver::from_driver(StepperDriver::new(in1, in2, in3, in4), sleep, fault)
     }
 }
 
@@ -310,59 +706,338 @@ impl<DRIVER, SLEEP, FAULT> MotorDriver<DRIVER, SLEEP, FAULT>
     where
         DRIVER: Driver,
         SLEEP: SetDutyCycle,
-        FAULT: InputPin,
+        FAULT: FaultPin,
 {
+    /// Sets the duty of the PWM on the eep pin, in percent.
+    ///
+    /// Returns [MotorDriverError::InvalidRange] if `percent` is above 100, without touching the PWM.
     pub fn set_duty_cycle(&mut self, percent: u8) -> Result<(), MotorDriverError> {
+        check_percent(percent);
+
+        if percent > 100 {
+            return Err(MotorDriverError::InvalidRange);
+        }
+
         self.sleep.set_duty_cycle_percent(percent).map_err(|_| MotorDriverError::UnableToSetDuty)?;
+        self.duty = percent;
+
+        Ok(())
+    }
+
+    /// Disables both bridges by driving the eep pin low, the same as a zero duty but stated as intent
+    /// rather than speed control. In the modes where the eep pin carries the PWM this is also the only
+    /// way to put the outputs in Hi-Z, as [MotorDriver::sleep] isn't available.
+    ///
+    /// Any following [MotorDriver::set_duty_cycle] with a non-zero percentage enables the bridges again.
+    #[must_use = "motor command result must be checked"]
+    pub fn disable_via_eep(&mut self) -> Result<(), MotorDriverError> {
+        self.sleep.set_duty_cycle_fully_off().map_err(|_| MotorDriverError::UnableToSetDuty)?;
+        self.duty = 0;
 
         Ok(())
     }
+
+    /// Returns the last duty percentage successfully applied through [MotorDriver::set_duty_cycle].
+    pub fn duty_cycle_percent(&self) -> u8 {
+        self.duty
+    }
+
+    /// Swaps in a new PWM handle for the eep pin, e.g. after reconfiguring its timer, returning the
+    /// previous one. The new handle's duty is left untouched until the next [MotorDriver::set_duty_cycle].
+    pub fn replace_pwm(&mut self, pwm: SLEEP) -> SLEEP {
+        core::mem::replace(&mut self.sleep, pwm)
+    }
 }
 
 impl<DRIVER, SLEEP, FAULT> MotorDriver<DRIVER, Option<SLEEP>, FAULT>
     where
-        DRIVER: Driver,
+        DRIVER: Driver + Breaks,
         SLEEP: OutputPin,
-        FAULT: InputPin,
+        FAULT: FaultPin,
 {
     /// Puts the device into a low power sleep state, In this state, the H-bridges are disabled, the
     /// gate drive charge pump is stopped, all internal logic is reset, and all internal clocks are
     /// stopped. All inputs are ignored until [MotorDriver::wakeup] is called.
+    ///
+    /// With [SleepBehavior::CoastThenSleep] both bridges are coasted before nSLEEP is asserted.
+    #[must_use = "motor command result must be checked"]
     pub fn sleep(&mut self) -> Result<(), MotorDriverError> {
+        self.require_sleep_control()?;
+
+        if self.sleep_behavior == SleepBehavior::CoastThenSleep {
+            self.driver.coast()?;
+        }
+
         if let Some(sleep) = &mut self.sleep {
-            sleep.set_low().map_err(|_| MotorDriverError::GpioError)
-        } else {
-            Ok(())
+            sleep.set_low().map_err(|_| MotorDriverError::GpioError)?;
         }
+
+        self.awake = false;
+
+        Ok(())
     }
 
     /// Wake up the device from sleep mode.
+    #[must_use = "motor command result must be checked"]
     pub fn wakeup(&mut self) -> Result<(), MotorDriverError> {
+        self.require_sleep_control()?;
+
         if let Some(sleep) = &mut self.sleep {
-            sleep.set_high().map_err(|_| MotorDriverError::GpioError)
-        } else {
-            Ok(())
+            sleep.set_high().map_err(|_| MotorDriverError::GpioError)?;
+        }
+
+        self.awake = true;
+
+        Ok(())
+    }
+
+    /// Wakes the device up and then waits for its charge pump to settle, see [MotorDriver::set_wake_settle_us],
+    /// so the outputs can be driven at full strength as soon as this returns.
+    ///
+    /// Nothing is waited for without a sleep pin, as nSLEEP is then expected to be tied high.
+    #[must_use = "motor command result must be checked"]
+    pub fn wakeup_and_wait<DELAY: DelayNs>(&mut self, delay: &mut DELAY) -> Result<(), MotorDriverError> {
+        self.wakeup()?;
+
+        if self.sleep.is_some() {
+            delay.delay_us(self.wake_settle_us);
         }
+
+        Ok(())
     }
 }
 
-impl<DRIVER, PWM, FAULT> MotorDriver<DRIVER, PWM, FAULT>
+impl<DRIVER, SLEEP, FAULT> MotorDriver<DRIVER, Option<SLEEP>, FAULT>
     where
-        DRIVER: Driver,
-        FAULT: InputPin,
+        DRIVER: Driver + Breaks + BridgeStates,
+        SLEEP: OutputPin,
+        FAULT: FaultPin,
 {
-    /// Logic low when in fault condition (over-temperature, over-current)
-    pub fn is_faulty(&mut self) -> Result<bool, MotorDriverError> {
-        if let Some(fault) = &mut self.fault {
-            fault.is_low().map_err(|_| MotorDriverError::GpioError)
-        } else {
-            Ok(false)
+    /// Records the state of both bridges, coasts them and puts the device to sleep, regardless of the
+    /// [SleepBehavior]. The recorded states are applied again by [MotorDriver::wakeup_restore].
+    #[must_use = "motor command result must be checked"]
+    pub fn deep_sleep(&mut self) -> Result<(), MotorDriverError> {
+        self.require_sleep_control()?;
+
+        let states = self.driver.bridge_states();
+
+        self.driver.coast()?;
+
+        if let Some(sleep) = &mut self.sleep {
+            sleep.set_low().map_err(|_| MotorDriverError::GpioError)?;
         }
+
+        self.snapshot = Some(states);
+        self.awake = false;
+
+        Ok(())
     }
-}
 
-/// Represents all possible errors that may occur during the utilization of this crate.
-#[derive(Debug)]
+    /// Wakes the device up and drives both bridges back to the states recorded by the last
+    /// [MotorDriver::deep_sleep], if any. The bridges are left coasting when there is nothing to restore.
+    ///
+    /// Note the DRV8833 ignores its inputs for up to 1ms after waking up, so bridges which must start
+    /// moving immediately may need to be woken with [MotorDriver::wakeup_and_wait] and restored manually.
+    #[must_use = "motor command result must be checked"]
+    pub fn wakeup_restore(&mut self) -> Result<(), MotorDriverError> {
+        self.wakeup()?;
+
+        match self.snapshot.take() {
+            Some(states) => self.driver.restore_bridge_states(states),
+            None => Ok(()),
+        }
+    }
+}
+
+impl<DRIVER, SLEEP, FAULT> MotorDriver<DRIVER, Option<SLEEP>, FAULT>
+    where
+        DRIVER: Driver,
+        SLEEP: OutputPin,
+        FAULT: FaultPin,
+{
+    /// Configures how the IN pins are handled when [MotorDriver::sleep] is called.
+    pub fn set_sleep_behavior(&mut self, behavior: SleepBehavior) {
+        self.sleep_behavior = behavior;
+    }
+
+    /// Sets how long [MotorDriver::wakeup_and_wait] waits after driving nSLEEP high, in microseconds.
+    /// Defaults to the 1ms wake-up time of the DRV8833 datasheet.
+    pub fn set_wake_settle_us(&mut self, us: u32) {
+        self.wake_settle_us = us;
+    }
+
+    /// Returns whether the device is awake, as tracked from the last [MotorDriver::sleep] or
+    /// [MotorDriver::wakeup] call. Without a sleep pin nSLEEP is expected to be tied high, so the
+    /// device is always awake.
+    pub fn is_awake(&self) -> bool {
+        self.sleep.is_none() || self.awake
+    }
+
+    /// Makes [MotorDriver::sleep], [MotorDriver::wakeup] and [MotorDriver::deep_sleep] return
+    /// [MotorDriverError::NoSleepControl] when there is no sleep pin, instead of silently succeeding.
+    pub fn set_strict_sleep(&mut self, strict: bool) {
+        self.strict_sleep = strict;
+    }
+
+    fn require_sleep_control(&self) -> Result<(), MotorDriverError> {
+        match self.strict_sleep && self.sleep.is_none() {
+            true => Err(MotorDriverError::NoSleepControl),
+            false => Ok(()),
+        }
+    }
+}
+
+impl<DRIVER, SLEEP, FAULT> MotorDriver<DRIVER, Option<SLEEP>, FAULT>
+    where
+        DRIVER: Driver,
+        SLEEP: StatefulOutputPin,
+        FAULT: FaultPin,
+{
+    /// Returns whether nSLEEP is high according to the sleep pin rather than this driver's own tracking,
+    /// e.g. when the pin is shared with other drivers through a [SharedSleep](crate::SharedSleep). Without a
+    /// sleep pin the device is always awake, as for [MotorDriver::is_awake].
+    #[must_use = "the sleep state read from the pin should be checked"]
+    pub fn read_awake(&mut self) -> Result<bool, MotorDriverError> {
+        match &mut self.sleep {
+            Some(sleep) => sleep.is_set_high().map_err(|_| MotorDriverError::GpioError),
+            None => Ok(true),
+        }
+    }
+}
+
+#[cfg(not(feature = "no-fault"))]
+impl<DRIVER, SLEEP, FAULT> MotorDriver<DRIVER, Option<SLEEP>, FAULT>
+    where
+        DRIVER: Driver,
+        SLEEP: OutputPin,
+        FAULT: InputPin,
+{
+    /// Returns both the awake and fault state of the device in a single call, e.g. for a status LED.
+    #[must_use = "the device status should be checked"]
+    pub fn status(&mut self) -> Result<DriverStatus, MotorDriverError> {
+        Ok(DriverStatus {
+            awake: self.is_awake(),
+            faulted: self.is_faulty()?,
+        })
+    }
+}
+
+#[cfg(not(feature = "no-fault"))]
+impl<DRIVER, SLEEP, FAULT> MotorDriver<DRIVER, Option<SLEEP>, FAULT>
+    where
+        DRIVER: Driver + Breaks,
+        SLEEP: OutputPin,
+        FAULT: InputPin,
+{
+    /// Wakes the device up, waits for its charge pump to settle as [MotorDriver::wakeup_and_wait] does
+    /// and then confirms it is ready by reading the fault pins.
+    ///
+    /// Returns [MotorDriverError::WakeupFailed] if a fault is still reported after the settle time, e.g.
+    /// when the charge pump can't come up. The device is left awake either way, so the cause can be
+    /// inspected, or [MotorDriver::sleep] called to give up.
+    #[must_use = "motor command result must be checked"]
+    pub fn try_wakeup<DELAY: DelayNs>(&mut self, delay: &mut DELAY) -> Result<(), MotorDriverError> {
+        self.wakeup_and_wait(delay)?;
+
+        match self.is_faulty()? {
+            true => Err(MotorDriverError::WakeupFailed),
+            false => Ok(()),
+        }
+    }
+}
+
+/// A snapshot of the device state, see [MotorDriver::status].
+#[cfg(not(feature = "no-fault"))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DriverStatus {
+    /// Whether the device is awake, see [MotorDriver::is_awake].
+    pub awake: bool,
+    /// Whether the fault pin reports a fault, see [MotorDriver::is_faulty].
+    pub faulted: bool,
+}
+
+#[cfg(not(feature = "no-fault"))]
+impl<DRIVER, PWM, FAULT> MotorDriver<DRIVER, PWM, FAULT>
+    where
+        DRIVER: Driver,
+        FAULT: InputPin,
+{
+    /// Logic low when in fault condition (over-temperature, over-current)
+    ///
+    /// With a [separate fault pin for bridge B](MotorDriver::set_fault_b), returns whether either pin
+    /// reports a fault.
+    #[must_use = "motor command result must be checked"]
+    pub fn is_faulty(&mut self) -> Result<bool, MotorDriverError> {
+        Ok(self.is_faulty_a()? | self.is_faulty_b()?)
+    }
+
+    /// Returns whether the fault pin given at construction reports a fault. Without a
+    /// [separate fault pin for bridge B](MotorDriver::set_fault_b) it covers both bridges.
+    #[must_use = "the fault state should be checked"]
+    pub fn is_faulty_a(&mut self) -> Result<bool, MotorDriverError> {
+        Self::read_fault(&mut self.fault)
+    }
+
+    /// Returns whether the fault pin of bridge B reports a fault, or `false` if none was set.
+    #[must_use = "the fault state should be checked"]
+    pub fn is_faulty_b(&mut self) -> Result<bool, MotorDriverError> {
+        Self::read_fault(&mut self.fault_b)
+    }
+
+    /// Sets a separate fault pin for bridge B, for designs routing a fault indicator per bridge, e.g.
+    /// when each bridge is on its own chip. The fault pin given at construction then covers bridge A only.
+    pub fn set_fault_b(&mut self, fault: Option<FAULT>) {
+        self.fault_b = fault;
+    }
+
+    fn read_fault(fault: &mut Option<FAULT>) -> Result<bool, MotorDriverError> {
+        match fault {
+            Some(fault) => fault.is_low().map_err(|_| MotorDriverError::GpioError),
+            None => Ok(false),
+        }
+    }
+
+    /// Polls the fault pin once every millisecond over a window of `ms` milliseconds and returns
+    /// `true` only if the fault was reported on every read. This distinguishes a stalled motor from
+    /// a momentary over-current trip, such as the inrush current when starting.
+    #[must_use = "the fault state should be checked"]
+    pub fn fault_sustained<DELAY: DelayNs>(&mut self, ms: u32, delay: &mut DELAY) -> Result<bool, MotorDriverError> {
+        for _ in 0..ms {
+            if !self.is_faulty()? {
+                return Ok(false);
+            }
+
+            delay.delay_ms(1);
+        }
+
+        self.is_faulty()
+    }
+
+    /// Reads the fault pins once through `debouncer`, returning the fault state only once it was
+    /// reported by enough consecutive calls, see [FaultDebouncer].
+    #[must_use = "the fault state should be checked"]
+    pub fn is_faulty_debounced(&mut self, debouncer: &mut FaultDebouncer) -> Result<bool, MotorDriverError> {
+        Ok(debouncer.update(self.is_faulty()?))
+    }
+}
+
+/// Represents all possible errors that may occur during the utilization of this crate.
+///
+/// New variants may be added in minor releases, so matching on it requires a wildcard arm:
+///
+/// ```compile_fail
+/// use drv8833_driver::MotorDriverError;
+///
+/// fn is_retryable(error: MotorDriverError) -> bool {
+///     match error {
+///         MotorDriverError::GpioError | MotorDriverError::UnableToSetDuty | MotorDriverError::PwmLocked => true,
+///         MotorDriverError::InvalidRange | MotorDriverError::CapacityExceeded | MotorDriverError::NoSleepControl => false,
+///         MotorDriverError::ConflictingLegs | MotorDriverError::Unsupported | MotorDriverError::WakeupFailed => false,
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
 pub enum MotorDriverError {
     /// Returned when fail to set pin low/high.
     GpioError,
@@ -372,26 +1047,392 @@ pub enum MotorDriverError {
     PwmLocked,
     /// Returned when in PWM mode and a duty value is not within 0-100 range.
     InvalidRange,
+    /// Returned when a fixed capacity container, e.g. the emergency stop registry, is full.
+    CapacityExceeded,
+    /// Returned by the sleep commands in strict mode when no sleep pin was provided.
+    NoSleepControl,
+    /// Returned in parallel mode when the two legs would be commanded into states driving against each other.
+    ConflictingLegs,
+    /// Returned by runtime dispatched drivers such as [AnyDriver](crate::AnyDriver) when an operation doesn't
+    /// apply to the active mode.
+    Unsupported,
+    /// Returned by [MotorDriver::try_wakeup] when the device still reports a fault once awake.
+    WakeupFailed,
+}
+
+impl Display for MotorDriverError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            MotorDriverError::GpioError => write!(f, "failed to set pin level"),
+            MotorDriverError::UnableToSetDuty => write!(f, "failed to set duty cycle"),
+            MotorDriverError::PwmLocked => write!(f, "unable to acquire the pwm lock"),
+            MotorDriverError::InvalidRange => write!(f, "duty percentage is not within 0-100 range"),
+            MotorDriverError::CapacityExceeded => write!(f, "capacity exceeded"),
+            MotorDriverError::NoSleepControl => write!(f, "no sleep pin to control"),
+            MotorDriverError::ConflictingLegs => write!(f, "parallel legs would drive against each other"),
+            MotorDriverError::Unsupported => write!(f, "operation not supported in this mode"),
+            MotorDriverError::WakeupFailed => write!(f, "device still reports a fault after waking up"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for MotorDriverError {}
+
+/// Flags a speed percentage above 100 as a programmer error, panicking in debug builds when the
+/// `panic-on-invalid` feature is enabled. Otherwise does nothing, leaving the caller to clamp it or return
+/// [MotorDriverError::InvalidRange] as it documents.
+pub(crate) fn check_percent(percent: u8) {
+    debug_assert!(
+        !cfg!(feature = "panic-on-invalid") || percent <= 100,
+        "speed percentage {} is above 100",
+        percent
+    );
+}
+
+/// Returned by the `try_new_*` constructors when some of the IN pins could not be initialized, listing
+/// every pin which failed instead of only the first one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PinInitError {
+    /// Whether initializing IN1, IN2, IN3 and IN4 failed, in order.
+    pub failed: [bool; 4],
+}
+
+impl PinInitError {
+    /// Returns the numbers of the IN pins which failed, e.g. `2` for IN2.
+    pub fn failed_pins(&self) -> impl Iterator<Item = u8> + '_ {
+        (1..=4).filter(|pin| self.failed[*pin as usize - 1])
+    }
+}
+
+impl Display for PinInitError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(f, "failed to initialize")?;
+
+        for pin in self.failed_pins() {
+            write!(f, " IN{}", pin)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for PinInitError {}
+
+impl From<PinInitError> for MotorDriverError {
+    fn from(_: PinInitError) -> Self {
+        MotorDriverError::GpioError
+    }
+}
+
+/// Drives every IN pin low, carrying on past failures so all of them are reported.
+fn coast_pins<IN1, IN2, IN3, IN4>(in1: &mut IN1, in2: &mut IN2, in3: &mut IN3, in4: &mut IN4) -> Result<(), PinInitError>
+    where
+        IN1: OutputPin,
+        IN2: OutputPin,
+        IN3: OutputPin,
+        IN4: OutputPin,
+{
+    let failed = [in1.set_low().is_err(), in2.set_low().is_err(), in3.set_low().is_err(), in4.set_low().is_err()];
+
+    match failed.contains(&true) {
+        true => Err(PinInitError { failed }),
+        false => Ok(()),
+    }
+}
+
+/// The last state successfully commanded to a bridge.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MotorState {
+    /// Both inputs low, the motor spins freely. Bridges start in this state.
+    #[default]
+    Coast,
+    /// Both inputs high, the motor is braked.
+    Stop,
+    /// Driving forward at the given percentage, digital bridges always report `100`.
+    Forward(u8),
+    /// Driving in reverse at the given percentage, digital bridges always report `100`.
+    Reverse(u8),
+}
+
+impl MotorState {
+    /// The logical levels of IN1 and IN2 for this state, according to the DRV8833 truth table.
+    ///
+    /// PWM states report the level of the pin carrying the signal as high.
+    pub fn pin_states(&self) -> [PinState; 2] {
+        match self {
+            MotorState::Coast => [PinState::Low, PinState::Low],
+            MotorState::Stop => [PinState::High, PinState::High],
+            MotorState::Forward(_) => [PinState::High, PinState::Low],
+            MotorState::Reverse(_) => [PinState::Low, PinState::High],
+        }
+    }
+
+    /// The commanded speed percentage, `0` when coasting or braked.
+    pub fn percent(&self) -> u8 {
+        match self {
+            MotorState::Forward(percent) | MotorState::Reverse(percent) => *percent,
+            MotorState::Coast | MotorState::Stop => 0,
+        }
+    }
+
+    /// Issues the command recreating this state on `motor`, e.g. to restore a snapshot taken through
+    /// [PwmBridge::state](crate::PwmBridge::state).
+    #[must_use = "motor command result must be checked"]
+    pub fn apply_to<MOTOR>(&self, motor: &mut MOTOR) -> Result<(), MotorDriverError>
+        where
+            MOTOR: PwmMovement + Breaks + ?Sized,
+    {
+        match *self {
+            MotorState::Coast => motor.coast(),
+            MotorState::Stop => motor.stop(),
+            MotorState::Forward(percent) => motor.forward(percent),
+            MotorState::Reverse(percent) => motor.reverse(percent),
+        }
+    }
+}
+
+impl Display for MotorState {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            MotorState::Coast => write!(f, "coast"),
+            MotorState::Stop => write!(f, "stop"),
+            MotorState::Forward(percent) => write!(f, "fwd {}%", percent),
+            MotorState::Reverse(percent) => write!(f, "rev {}%", percent),
+        }
+    }
+}
+
+/// The direction of rotation of a motor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    Forward,
+    Reverse,
+}
+
+impl Display for Direction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Direction::Forward => write!(f, "fwd"),
+            Direction::Reverse => write!(f, "rev"),
+        }
+    }
+}
+
+/// How the winding current decays during the off-time of a PWM signal, see the "Bridge Control and Decay
+/// Modes" section of the datasheet.
+///
+/// Bridges use [Decay::Fast] unless the `default-slow-decay` feature is enabled.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Decay {
+    /// The PWM signal is applied to one input while the other is held low, the bridge coasts during
+    /// the off-time. Gives a more linear speed response.
+    #[cfg_attr(not(feature = "default-slow-decay"), default)]
+    Fast,
+    /// One input is held high while the other carries the inverted PWM signal, the bridge brakes during
+    /// the off-time. Gives more torque at low speed and less ripple.
+    #[cfg_attr(feature = "default-slow-decay", default)]
+    Slow,
+}
+
+impl Display for Decay {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Decay::Fast => write!(f, "fast decay"),
+            Decay::Slow => write!(f, "slow decay"),
+        }
+    }
 }
 
 /// A trait representing movement control for motors via PWM signal.
 pub trait PwmMovement {
     /// Sets the motor direction to forward with a given percentage of speed.
+    #[must_use = "motor command result must be checked"]
     fn forward(&mut self, percent: u8) -> Result<(), MotorDriverError>;
 
     /// Sets the motor direction to reverse with a given percentage of speed.
+    #[must_use = "motor command result must be checked"]
     fn reverse(&mut self, percent: u8) -> Result<(), MotorDriverError>;
 }
 
 /// A trait representing movement control for motors.
 pub trait Movement {
     /// This method instructs the motor to move in the forward direction.
+    #[must_use = "motor command result must be checked"]
     fn forward(&mut self) -> Result<(), MotorDriverError>;
 
     /// This method instructs the motor to move in the reverse direction.
+    #[must_use = "motor command result must be checked"]
     fn reverse(&mut self) -> Result<(), MotorDriverError>;
 }
 
+impl<T: PwmMovement + ?Sized> PwmMovement for &mut T {
+    fn forward(&mut self, percent: u8) -> Result<(), MotorDriverError> {
+        T::forward(self, percent)
+    }
+
+    fn reverse(&mut self, percent: u8) -> Result<(), MotorDriverError> {
+        T::reverse(self, percent)
+    }
+}
+
+impl<T: Movement + ?Sized> Movement for &mut T {
+    fn forward(&mut self) -> Result<(), MotorDriverError> {
+        T::forward(self)
+    }
+
+    fn reverse(&mut self) -> Result<(), MotorDriverError> {
+        T::reverse(self)
+    }
+}
+
+impl<T: Breaks + ?Sized> Breaks for &mut T {
+    fn coast(&mut self) -> Result<(), MotorDriverError> {
+        T::coast(self)
+    }
+
+    fn stop(&mut self) -> Result<(), MotorDriverError> {
+        T::stop(self)
+    }
+}
+
+/// Commands an optional motor, e.g. a second motor which may not be populated, doing nothing when
+/// it is `None`.
+impl<T: PwmMovement> PwmMovement for Option<T> {
+    fn forward(&mut self, percent: u8) -> Result<(), MotorDriverError> {
+        self.as_mut().map_or(Ok(()), |motor| motor.forward(percent))
+    }
+
+    fn reverse(&mut self, percent: u8) -> Result<(), MotorDriverError> {
+        self.as_mut().map_or(Ok(()), |motor| motor.reverse(percent))
+    }
+}
+
+/// See the [PwmMovement] implementation for `Option`.
+impl<T: Movement> Movement for Option<T> {
+    fn forward(&mut self) -> Result<(), MotorDriverError> {
+        self.as_mut().map_or(Ok(()), |motor| motor.forward())
+    }
+
+    fn reverse(&mut self) -> Result<(), MotorDriverError> {
+        self.as_mut().map_or(Ok(()), |motor| motor.reverse())
+    }
+}
+
+/// See the [PwmMovement] implementation for `Option`.
+impl<T: Breaks> Breaks for Option<T> {
+    fn coast(&mut self) -> Result<(), MotorDriverError> {
+        self.as_mut().map_or(Ok(()), |motor| motor.coast())
+    }
+
+    fn stop(&mut self) -> Result<(), MotorDriverError> {
+        self.as_mut().map_or(Ok(()), |motor| motor.stop())
+    }
+}
+
+/// An object-safe facade over [Movement], [PwmMovement] and [Breaks], allowing motors driven in
+/// different modes to be stored together, e.g. in a `Vec<Box<dyn DynMotor>>`.
+///
+/// Digital modes have no speed control, so any non-zero `percent` drives them at full speed and
+/// zero coasts.
+pub trait DynMotor {
+    /// Sets the motor direction to forward with a given percentage of speed.
+    #[must_use = "motor command result must be checked"]
+    fn forward(&mut self, percent: u8) -> Result<(), MotorDriverError>;
+
+    /// Sets the motor direction to reverse with a given percentage of speed.
+    #[must_use = "motor command result must be checked"]
+    fn reverse(&mut self, percent: u8) -> Result<(), MotorDriverError>;
+
+    /// See [Breaks::coast].
+    #[must_use = "motor command result must be checked"]
+    fn coast(&mut self) -> Result<(), MotorDriverError>;
+
+    /// See [Breaks::stop].
+    #[must_use = "motor command result must be checked"]
+    fn stop(&mut self) -> Result<(), MotorDriverError>;
+}
+
+impl<T: DynMotor + ?Sized> DynMotor for &mut T {
+    fn forward(&mut self, percent: u8) -> Result<(), MotorDriverError> {
+        T::forward(self, percent)
+    }
+
+    fn reverse(&mut self, percent: u8) -> Result<(), MotorDriverError> {
+        T::reverse(self, percent)
+    }
+
+    fn coast(&mut self) -> Result<(), MotorDriverError> {
+        T::coast(self)
+    }
+
+    fn stop(&mut self) -> Result<(), MotorDriverError> {
+        T::stop(self)
+    }
+}
+
+/// See the [PwmMovement] implementation for `Option`.
+impl<T: DynMotor> DynMotor for Option<T> {
+    fn forward(&mut self, percent: u8) -> Result<(), MotorDriverError> {
+        self.as_mut().map_or(Ok(()), |motor| motor.forward(percent))
+    }
+
+    fn reverse(&mut self, percent: u8) -> Result<(), MotorDriverError> {
+        self.as_mut().map_or(Ok(()), |motor| motor.reverse(percent))
+    }
+
+    fn coast(&mut self) -> Result<(), MotorDriverError> {
+        self.as_mut().map_or(Ok(()), |motor| motor.coast())
+    }
+
+    fn stop(&mut self) -> Result<(), MotorDriverError> {
+        self.as_mut().map_or(Ok(()), |motor| motor.stop())
+    }
+}
+
+impl<DRIVER, SLEEP, FAULT> DynMotor for MotorDriver<DRIVER, SLEEP, FAULT>
+    where
+        DRIVER: Driver + DynMotor,
+        FAULT: FaultPin,
+{
+    fn forward(&mut self, percent: u8) -> Result<(), MotorDriverError> {
+        self.driver.forward(percent)
+    }
+
+    fn reverse(&mut self, percent: u8) -> Result<(), MotorDriverError> {
+        self.driver.reverse(percent)
+    }
+
+    fn coast(&mut self) -> Result<(), MotorDriverError> {
+        self.driver.coast()
+    }
+
+    fn stop(&mut self) -> Result<(), MotorDriverError> {
+        self.driver.stop()
+    }
+}
+
+/// Implemented by the drivers with two independently controlled bridges, so their states can be
+/// recorded and restored across a [MotorDriver::deep_sleep].
+pub trait BridgeStates {
+    /// Returns the last state commanded to bridge A and B.
+    fn bridge_states(&self) -> [MotorState; 2];
+
+    /// Commands each bridge to the given state.
+    #[must_use = "motor command result must be checked"]
+    fn restore_bridge_states(&mut self, states: [MotorState; 2]) -> Result<(), MotorDriverError>;
+}
+
+/// Implemented by the drive modes which can report the load they currently command, used by
+/// [MotorDriver::estimated_current_ma].
+pub trait CurrentLoad {
+    /// Returns the sum of the duty percentages commanded to each output, e.g. `150` with one motor at
+    /// full speed and the other at half. Paralleled outputs count twice, as they share one motor drawing
+    /// twice the current. Braking and coasting outputs count as zero.
+    fn load_percent(&self) -> u16;
+}
+
 /// A trait representing braking control for motors.
 pub trait Breaks {
     /// Sets the motor driver to coast mode, allowing the motor to freely spin or coast to a stop
@@ -401,14 +1442,254 @@ pub trait Breaks {
     /// Coast mode is useful when a smooth and natural deceleration of the motor is desired, such as
     /// when transitioning between motor states or when manual control requires the motor to spin
     /// freely without any active driving or braking.
+    #[must_use = "motor command result must be checked"]
     fn coast(&mut self) -> Result<(), MotorDriverError>;
 
     /// Sets the motor driver to stop mode, causing the motor to rapidly come to a halt by
-    /// applying a fast decay to the current in the motor winding. In fast decay, the magnetic field
-    /// around the motor winding collapses quickly when the motor driver switches off, resulting in
-    /// rapid deceleration. This mode is beneficial for achieving fast motor response times and
-    /// transitioning between motor states quickly. However, it may produce higher levels of
-    /// electrical noise due to the rapid changes in current. Use stop mode when immediate stopping
-    /// of the motor is required, accepting the trade-off of potential electrical noise.
+    /// driving both inputs high. On the DRV8833 this turns on both low-side FETs, shorting the
+    /// motor terminals together so the winding current recirculates and decays slowly (brake).
+    /// The back-EMF of the spinning motor is dissipated in the winding, resulting in rapid
+    /// deceleration. This mode is beneficial for achieving fast motor response times and
+    /// transitioning between motor states quickly. Use stop mode when immediate stopping
+    /// of the motor is required.
+    #[must_use = "motor command result must be checked"]
     fn stop(&mut self) -> Result<(), MotorDriverError>;
+
+    /// Brakes the motor to halt it, e.g. on an incline, waits `ms` milliseconds for it to stop and
+    /// then coasts it so no power is drawn while standing still.
+    ///
+    /// If braking is rejected with [MotorDriverError::Unsupported], e.g. by [BrakePolicy::Reject], the motor
+    /// is coasted right away and the error returned.
+    #[must_use = "motor command result must be checked"]
+    fn brake_then_coast<DELAY: DelayNs>(&mut self, ms: u32, delay: &mut DELAY) -> Result<(), MotorDriverError>
+        where
+            Self: Sized,
+    {
+        if let Err(error) = self.stop() {
+            if error == MotorDriverError::Unsupported {
+                self.coast()?;
+            }
+
+            return Err(error);
+        }
+
+        delay.delay_ms(ms);
+
+        self.coast()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{HashMap, HashSet};
+    #[cfg(feature = "std")]
+    use std::error::Error;
+
+    #[cfg(not(feature = "no-fault"))]
+    use embedded_hal_mock::eh1::delay::NoopDelay;
+    use embedded_hal_mock::eh1::pin::Mock as Pin;
+    use embedded_hal_mock::eh1::pin::State::{High, Low};
+    use embedded_hal_mock::eh1::pin::Transaction;
+    use embedded_hal_mock::eh1::pwm::Mock as PwmPin;
+    use embedded_hal_mock::eh1::pwm::Transaction as PwmPinTransaction;
+
+    use crate::driver::{Decay, Direction, MotorDriver, MotorDriverError, MotorState};
+    #[cfg(feature = "std")]
+    use crate::driver::DynMotor;
+    #[cfg(not(feature = "no-fault"))]
+    use crate::driver::DriverStatus;
+    #[cfg(not(feature = "no-fault"))]
+    use crate::mock_pins::MockPins;
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_different_modes_can_be_stored_behind_dyn_motor() -> Result<(), MotorDriverError> {
+        let mut pins = [
+            Pin::new(&[Transaction::set(High), Transaction::set(Low)]),
+            Pin::new(&[Transaction::set(Low), Transaction::set(Low)]),
+            Pin::new(&[Transaction::set(High), Transaction::set(Low)]),
+            Pin::new(&[Transaction::set(Low), Transaction::set(Low)]),
+            Pin::new(&[Transaction::set(Low), Transaction::set(Low)]),
+            Pin::new(&[Transaction::set(High), Transaction::set(Low)]),
+            Pin::new(&[Transaction::set(Low), Transaction::set(Low)]),
+            Pin::new(&[Transaction::set(High), Transaction::set(Low)]),
+        ];
+
+        let mut pwm = PwmPin::new(&[
+            PwmPinTransaction::max_duty_cycle(100),
+            PwmPinTransaction::set_duty_cycle(50),
+            PwmPinTransaction::set_duty_cycle(0),
+        ]);
+
+        let [in1, in2, in3, in4, in5, in6, in7, in8] = pins.clone();
+
+        let mut motors: Vec<Box<dyn DynMotor>> = vec![
+            Box::new(MotorDriver::new_parallel(in1, in2, in3, in4, None::<Pin>, None::<Pin>)),
+            Box::new(MotorDriver::new_pwm_parallel(in5, in6, in7, in8, pwm.clone(), None::<Pin>)),
+        ];
+
+        motors[0].forward(100)?;
+        motors[1].reverse(50)?;
+
+        for motor in motors.iter_mut() {
+            motor.coast()?;
+        }
+
+        for pin in pins.iter_mut() {
+            pin.done();
+        }
+
+        pwm.done();
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_disable_via_eep_turns_the_pwm_fully_off() -> Result<(), MotorDriverError> {
+        let mut pin = Pin::new(&[]);
+        let mut pwm = PwmPin::new(&[
+            PwmPinTransaction::max_duty_cycle(100),
+            PwmPinTransaction::set_duty_cycle(60),
+            PwmPinTransaction::set_duty_cycle(0),
+        ]);
+
+        let mut motor = MotorDriver::new_pwm_split_single(
+            pin.clone(), pin.clone(), pin.clone(), pin.clone(), pwm.clone(), None::<Pin>,
+        );
+
+        #[cfg(not(feature = "panic-on-invalid"))]
+        assert!(matches!(motor.set_duty_cycle(150), Err(MotorDriverError::InvalidRange)));
+
+        motor.set_duty_cycle(60)?;
+        motor.disable_via_eep()?;
+
+        assert_eq!(motor.duty_cycle_percent(), 0);
+
+        pin.done();
+        pwm.done();
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_states_can_be_used_as_map_keys() {
+        let mut counts = HashMap::new();
+
+        for state in [MotorState::Forward(50), MotorState::Coast, MotorState::Forward(50)] {
+            *counts.entry(state).or_insert(0) += 1;
+        }
+
+        assert_eq!(counts[&MotorState::Forward(50)], 2);
+        assert_eq!(HashSet::from([Direction::Forward, Direction::Forward]).len(), 1);
+        assert_eq!(HashSet::from([Decay::Fast, Decay::Slow]).len(), 2);
+    }
+
+    #[test]
+    fn test_states_display_as_short_strings() {
+        assert_eq!(MotorState::Forward(50).to_string(), "fwd 50%");
+        assert_eq!(MotorState::Reverse(100).to_string(), "rev 100%");
+        assert_eq!(MotorState::Coast.to_string(), "coast");
+        assert_eq!(MotorState::Stop.to_string(), "stop");
+        assert_eq!(Direction::Reverse.to_string(), "rev");
+        assert_eq!(Decay::Slow.to_string(), "slow decay");
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_errors_compose_into_boxed_errors() {
+        fn drive() -> Result<(), Box<dyn Error>> {
+            Err(MotorDriverError::InvalidRange)?
+        }
+
+        assert_eq!(drive().unwrap_err().to_string(), "duty percentage is not within 0-100 range");
+    }
+
+    #[cfg(not(feature = "no-fault"))]
+    #[test]
+    fn test_wakeup_fails_while_a_fault_is_reported() -> Result<(), MotorDriverError> {
+        let mut pins = MockPins::new([&[], &[], &[], &[]]).with_sleep(&[High, High]).with_fault(&[Low, High]);
+        let [in1, in2, in3, in4] = pins.ins();
+
+        let mut motor = MotorDriver::new_split(in1, in2, in3, in4, Some(pins.sleep()), Some(pins.fault()));
+
+        assert_eq!(motor.try_wakeup(&mut NoopDelay::new()), Err(MotorDriverError::WakeupFailed));
+        assert!(motor.is_awake());
+
+        motor.try_wakeup(&mut NoopDelay::new())?;
+
+        pins.done();
+
+        Ok(())
+    }
+
+    #[cfg(not(feature = "no-fault"))]
+    #[test]
+    fn test_status_reports_awake_and_fault_together() -> Result<(), MotorDriverError> {
+        let mut pins = MockPins::new([&[], &[], &[], &[]]).with_sleep(&[High]).with_fault(&[High, Low]);
+        let [in1, in2, in3, in4] = pins.ins();
+
+        let mut motor = MotorDriver::new_split(in1, in2, in3, in4, Some(pins.sleep()), Some(pins.fault()));
+
+        assert_eq!(motor.status()?, DriverStatus { awake: false, faulted: false });
+
+        motor.wakeup()?;
+
+        assert_eq!(motor.status()?, DriverStatus { awake: true, faulted: true });
+
+        pins.done();
+
+        Ok(())
+    }
+
+    #[cfg(not(feature = "no-fault"))]
+    #[test]
+    fn test_each_bridge_fault_can_be_queried_independently() -> Result<(), MotorDriverError> {
+        let mut pins = MockPins::new([&[], &[], &[], &[]]).with_fault(&[High, High, High]);
+        let mut fault_b = Pin::new(&[Transaction::get(Low), Transaction::get(Low)]);
+        let [in1, in2, in3, in4] = pins.ins();
+
+        let mut motor = MotorDriver::new_split(in1, in2, in3, in4, None::<Pin>, Some(pins.fault()));
+
+        motor.set_fault_b(Some(fault_b.clone()));
+
+        assert!(!motor.is_faulty_a()?);
+        assert!(motor.is_faulty_b()?);
+        assert!(motor.is_faulty()?);
+
+        motor.set_fault_b(None);
+
+        assert!(!motor.is_faulty()?);
+
+        pins.done();
+        fault_b.done();
+
+        Ok(())
+    }
+
+    #[cfg(not(feature = "no-fault"))]
+    #[test]
+    fn test_fault_must_persist_for_the_whole_window() -> Result<(), MotorDriverError> {
+        let mut fault = Pin::new(&[
+            Transaction::get(Low),
+            Transaction::get(Low),
+            Transaction::get(Low),
+            Transaction::get(Low),
+            Transaction::get(Low),
+            Transaction::get(High),
+        ]);
+
+        let mut pin = Pin::new(&[]);
+
+        let mut motor = MotorDriver::new_split(
+            pin.clone(), pin.clone(), pin.clone(), pin.clone(), None::<Pin>, Some(fault.clone()),
+        );
+
+        assert!(motor.fault_sustained(3, &mut NoopDelay::new())?);
+        assert!(!motor.fault_sustained(3, &mut NoopDelay::new())?);
+
+        pin.done();
+        fault.done();
+
+        Ok(())
+    }
 }
@@ -0,0 +1,122 @@
+use core::cell::RefCell;
+
+use critical_section::Mutex;
+
+use crate::driver::{Breaks, MotorDriverError};
+
+/// Maximum number of motors that can be registered with [register] at the same time.
+pub const MAX_REGISTERED: usize = 4;
+
+static REGISTRY: Mutex<RefCell<[Option<&'static dyn EmergencyCoast>; MAX_REGISTERED]>> =
+    Mutex::new(RefCell::new([None; MAX_REGISTERED]));
+
+/// Implemented by statically stored motors which can be coasted through a shared reference, so they
+/// can be stopped from contexts that don't own them, such as a panic handler.
+///
+/// It is implemented for any [Breaks] motor stored in a `critical_section::Mutex<RefCell<Option<_>>>`.
+pub trait EmergencyCoast: Sync {
+    /// Coasts the motor, ignoring any error as there is nothing left to report it to.
+    fn emergency_coast(&self);
+}
+
+impl<MOTOR: Breaks + Send> EmergencyCoast for Mutex<RefCell<Option<MOTOR>>> {
+    /// Does nothing if the motor is currently borrowed, e.g. when the panic happened in the middle of
+    /// a command, as there is no sound way to reach it.
+    fn emergency_coast(&self) {
+        critical_section::with(|cs| {
+            if let Ok(mut motor) = self.borrow(cs).try_borrow_mut() {
+                if let Some(motor) = motor.as_mut() {
+                    let _ = motor.coast();
+                }
+            }
+        });
+    }
+}
+
+/// Registers a statically stored motor to be coasted by [all_stop].
+///
+/// Available with the `panic-stop` feature.
+///
+/// Returns [MotorDriverError::CapacityExceeded] if [MAX_REGISTERED] motors are already registered.
+///
+/// # Example
+///
+/// ```ignore
+/// static MOTOR: Mutex<RefCell<Option<SplitDriver<Pin, Pin, Pin, Pin>>>> = Mutex::new(RefCell::new(None));
+///
+/// critical_section::with(|cs| MOTOR.borrow(cs).replace(Some(motor.into_driver())));
+/// emergency::register(&MOTOR)?;
+///
+/// #[panic_handler]
+/// fn panic(_: &PanicInfo) -> ! {
+///     emergency::all_stop();
+///     loop {}
+/// }
+/// ```
+pub fn register(motor: &'static dyn EmergencyCoast) -> Result<(), MotorDriverError> {
+    critical_section::with(|cs| {
+        let mut registry = REGISTRY.borrow_ref_mut(cs);
+        let slot = registry.iter_mut().find(|slot| slot.is_none()).ok_or(MotorDriverError::CapacityExceeded)?;
+
+        *slot = Some(motor);
+
+        Ok(())
+    })
+}
+
+/// Removes every registered motor.
+pub fn clear() {
+    critical_section::with(|cs| *REGISTRY.borrow_ref_mut(cs) = [None; MAX_REGISTERED]);
+}
+
+/// Coasts every motor registered with [register]. Safe to call from a panic handler.
+pub fn all_stop() {
+    let motors = critical_section::with(|cs| *REGISTRY.borrow_ref(cs));
+
+    for motor in motors.iter().flatten() {
+        motor.emergency_coast();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+
+    use critical_section::Mutex;
+    use embedded_hal_mock::eh1::pin::Mock as Pin;
+    use embedded_hal_mock::eh1::pin::State::{High, Low};
+    use embedded_hal_mock::eh1::pin::Transaction;
+
+    use crate::bridge::Bridge;
+    use crate::driver::{MotorDriverError, Movement};
+    use crate::emergency::{all_stop, clear, register, MAX_REGISTERED};
+
+    static MOTOR: Mutex<RefCell<Option<Bridge<Pin, Pin>>>> = Mutex::new(RefCell::new(None));
+
+    #[test]
+    fn test_registered_motor_is_coasted_by_all_stop() -> Result<(), MotorDriverError> {
+        let mut in1 = Pin::new(&[Transaction::set(High), Transaction::set(Low)]);
+        let mut in2 = Pin::new(&[Transaction::set(Low), Transaction::set(Low)]);
+
+        let mut bridge = Bridge::new(in1.clone(), in2.clone());
+
+        bridge.forward()?;
+
+        critical_section::with(|cs| MOTOR.borrow_ref_mut(cs).replace(bridge));
+
+        for _ in 0..MAX_REGISTERED {
+            register(&MOTOR)?;
+        }
+
+        assert!(matches!(register(&MOTOR), Err(MotorDriverError::CapacityExceeded)));
+
+        clear();
+        register(&MOTOR)?;
+        all_stop();
+
+        in1.done();
+        in2.done();
+
+        Ok(())
+    }
+}
@@ -0,0 +1,105 @@
+/// Adds hysteresis to a polled fault state, e.g. for a status display, so the reported state only
+/// flips after a number of consecutive polls agree on the new state.
+///
+/// Unlike [MotorDriver::fault_sustained](crate::MotorDriver::fault_sustained), which blocks over a time
+/// window, this counts calls and is meant to be fed once per iteration of a polling loop.
+///
+/// # Example
+///
+/// ```no_run
+/// # #[cfg(not(feature = "no-fault"))]
+/// # fn main() -> Result<(), drv8833_driver::MotorDriverError> {
+/// use drv8833_driver::{Breaks, FaultDebouncer, MotorDriver};
+/// use embedded_hal_mock::eh1::pin::Mock as Pin;
+///
+/// let (in1, in2, in3, in4) = (Pin::new(&[]), Pin::new(&[]), Pin::new(&[]), Pin::new(&[]));
+/// let fault = Pin::new(&[]);
+///
+/// let mut motor = MotorDriver::new_split(in1, in2, in3, in4, None::<Pin>, Some(fault));
+/// let mut debouncer = FaultDebouncer::new(3, 10);
+///
+/// loop {
+///     if motor.is_faulty_debounced(&mut debouncer)? {
+///         motor.coast()?;
+///     }
+/// }
+/// # }
+/// # #[cfg(feature = "no-fault")]
+/// # fn main() {}
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FaultDebouncer {
+    set_count: u16,
+    clear_count: u16,
+    streak: u16,
+    faulted: bool,
+}
+
+impl FaultDebouncer {
+    /// Creates a debouncer reporting a fault after `set_count` consecutive faulty reads, and clearing it
+    /// after `clear_count` consecutive healthy reads. A count of `0` or `1` flips on the first read.
+    pub fn new(set_count: u16, clear_count: u16) -> Self {
+        Self {
+            set_count,
+            clear_count,
+            streak: 0,
+            faulted: false,
+        }
+    }
+
+    /// Feeds a raw fault read and returns the debounced state.
+    pub fn update(&mut self, faulty: bool) -> bool {
+        if faulty == self.faulted {
+            self.streak = 0;
+
+            return self.faulted;
+        }
+
+        self.streak = self.streak.saturating_add(1);
+
+        let required = match faulty {
+            true => self.set_count,
+            false => self.clear_count,
+        };
+
+        if self.streak >= required {
+            self.faulted = faulty;
+            self.streak = 0;
+        }
+
+        self.faulted
+    }
+
+    /// Returns the debounced state without feeding a new read.
+    pub fn is_faulty(&self) -> bool {
+        self.faulted
+    }
+
+    /// Forgets the reads so far and reports no fault.
+    pub fn reset(&mut self) {
+        self.streak = 0;
+        self.faulted = false;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fault_debouncer::FaultDebouncer;
+
+    #[test]
+    fn test_state_flips_only_after_consistent_reads() {
+        let mut debouncer = FaultDebouncer::new(3, 2);
+
+        assert!(!debouncer.update(true));
+        assert!(!debouncer.update(true));
+        assert!(!debouncer.update(false));
+        assert!(!debouncer.update(true));
+        assert!(!debouncer.update(true));
+        assert!(debouncer.update(true));
+
+        assert!(debouncer.update(false));
+        assert!(debouncer.update(true));
+        assert!(debouncer.update(false));
+        assert!(!debouncer.update(false));
+    }
+}
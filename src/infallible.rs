@@ -0,0 +1,236 @@
+use core::convert::Infallible;
+
+use embedded_hal::digital::OutputPin;
+
+use crate::bridge::Bridge;
+use crate::driver::{Breaks, MotorDriverError, Movement};
+use crate::parallel_driver::ParallelDriver;
+
+/// Implemented by the digital drivers over pins which can't fail, as the GPIO types of most HALs whose `Error`
+/// is [Infallible], for which a pin error is the only way [Movement::forward], [Movement::reverse] and
+/// [Breaks::coast] can fail.
+///
+/// Implementing it for a driver whose movement commands have other failure modes makes [InfallibleMotor]
+/// silently discard them.
+pub trait InfalliblePins {}
+
+impl<IN1, IN2> InfalliblePins for Bridge<IN1, IN2>
+    where
+        IN1: OutputPin<Error = Infallible>,
+        IN2: OutputPin<Error = Infallible>,
+{}
+
+impl<IN1, IN2, IN3, IN4> InfalliblePins for ParallelDriver<IN1, IN2, IN3, IN4>
+    where
+        IN1: OutputPin<Error = Infallible>,
+        IN2: OutputPin<Error = Infallible>,
+        IN3: OutputPin<Error = Infallible>,
+        IN4: OutputPin<Error = Infallible>,
+{}
+
+impl<T: InfalliblePins + ?Sized> InfalliblePins for &mut T {}
+
+/// A motor over pins which can't fail, see [InfalliblePins], exposing the movement commands without a
+/// `Result` to handle.
+///
+/// Created by value with `into_infallible`, or borrowed with `infallible`, on a [Bridge] or a [ParallelDriver].
+/// The bridges of a split driver are wrapped one at a time, e.g. `motor.a.infallible().forward()`, and a
+/// [MotorDriver](crate::MotorDriver) reaches both through its driver.
+///
+/// [InfallibleMotor::stop] still returns a `Result`, as [BrakePolicy::Reject](crate::BrakePolicy::Reject)
+/// makes it fail with [MotorDriverError::Unsupported] regardless of the pins.
+///
+/// # Example
+///
+/// ```no_run
+/// use core::convert::Infallible;
+///
+/// use drv8833_driver::Bridge;
+/// use embedded_hal::digital::{ErrorType, OutputPin};
+///
+/// // Stands in for the GPIO type of a HAL which can't fail.
+/// struct Gpio;
+///
+/// impl ErrorType for Gpio {
+///     type Error = Infallible;
+/// }
+///
+/// impl OutputPin for Gpio {
+///     fn set_low(&mut self) -> Result<(), Infallible> {
+///         Ok(())
+///     }
+///
+///     fn set_high(&mut self) -> Result<(), Infallible> {
+///         Ok(())
+///     }
+/// }
+///
+/// let mut bridge = Bridge::new(Gpio, Gpio).into_infallible();
+///
+/// bridge.forward();
+/// bridge.coast();
+/// ```
+pub struct InfallibleMotor<MOTOR> {
+    motor: MOTOR,
+}
+
+/// A [Bridge] with infallible movement commands, see [InfallibleMotor].
+pub type InfallibleBridge<IN1, IN2> = InfallibleMotor<Bridge<IN1, IN2>>;
+
+impl<IN1, IN2> Bridge<IN1, IN2>
+    where
+        IN1: OutputPin<Error = Infallible>,
+        IN2: OutputPin<Error = Infallible>,
+{
+    /// Wraps this bridge into one exposing infallible movement commands, see [InfallibleMotor].
+    pub fn into_infallible(self) -> InfallibleBridge<IN1, IN2> {
+        InfallibleMotor { motor: self }
+    }
+
+    /// Borrows this bridge with infallible movement commands, see [InfallibleMotor].
+    pub fn infallible(&mut self) -> InfallibleMotor<&mut Self> {
+        InfallibleMotor { motor: self }
+    }
+}
+
+impl<IN1, IN2, IN3, IN4> ParallelDriver<IN1, IN2, IN3, IN4>
+    where
+        IN1: OutputPin<Error = Infallible>,
+        IN2: OutputPin<Error = Infallible>,
+        IN3: OutputPin<Error = Infallible>,
+        IN4: OutputPin<Error = Infallible>,
+{
+    /// Wraps this driver into one exposing infallible movement commands, see [InfallibleMotor].
+    pub fn into_infallible(self) -> InfallibleMotor<Self> {
+        InfallibleMotor { motor: self }
+    }
+
+    /// Borrows this driver with infallible movement commands, see [InfallibleMotor].
+    pub fn infallible(&mut self) -> InfallibleMotor<&mut Self> {
+        InfallibleMotor { motor: self }
+    }
+}
+
+impl<MOTOR: Movement + InfalliblePins> InfallibleMotor<MOTOR> {
+    #[inline]
+    pub fn forward(&mut self) {
+        let _ = self.motor.forward();
+    }
+
+    #[inline]
+    pub fn reverse(&mut self) {
+        let _ = self.motor.reverse();
+    }
+}
+
+impl<MOTOR: Breaks + InfalliblePins> InfallibleMotor<MOTOR> {
+    #[inline]
+    pub fn coast(&mut self) {
+        let _ = self.motor.coast();
+    }
+
+    /// Brakes the motor following its [BrakePolicy](crate::BrakePolicy), returning
+    /// [MotorDriverError::Unsupported] under [BrakePolicy::Reject](crate::BrakePolicy::Reject).
+    #[inline]
+    #[must_use = "motor command result must be checked"]
+    pub fn stop(&mut self) -> Result<(), MotorDriverError> {
+        self.motor.stop()
+    }
+}
+
+impl<MOTOR> InfallibleMotor<MOTOR> {
+    /// Returns the wrapped motor, e.g. to read its state.
+    pub fn get_ref(&self) -> &MOTOR {
+        &self.motor
+    }
+
+    /// Returns the wrapped motor, e.g. to reach the configuration methods.
+    pub fn into_inner(self) -> MOTOR {
+        self.motor
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::Infallible;
+
+    use embedded_hal::digital::{ErrorType, OutputPin, PinState};
+
+    use crate::bridge::Bridge;
+    use crate::driver::{BrakePolicy, MotorDriverError, MotorState};
+    use crate::parallel_driver::ParallelDriver;
+    use crate::split_driver::SplitDriver;
+
+    /// Stores the last level written, like a HAL pin which can't fail.
+    struct InfalliblePin(PinState);
+
+    impl ErrorType for InfalliblePin {
+        type Error = Infallible;
+    }
+
+    impl OutputPin for InfalliblePin {
+        fn set_low(&mut self) -> Result<(), Self::Error> {
+            self.0 = PinState::Low;
+
+            Ok(())
+        }
+
+        fn set_high(&mut self) -> Result<(), Self::Error> {
+            self.0 = PinState::High;
+
+            Ok(())
+        }
+    }
+
+    fn pin() -> InfalliblePin {
+        InfalliblePin(PinState::Low)
+    }
+
+    #[test]
+    fn test_infallible_pins_expose_commands_without_results() -> Result<(), MotorDriverError> {
+        let mut bridge = Bridge::new(pin(), pin()).into_infallible();
+
+        bridge.reverse();
+
+        assert_eq!(bridge.get_ref().state(), MotorState::Reverse(100));
+
+        bridge.stop()?;
+
+        let bridge = bridge.into_inner();
+
+        assert_eq!(bridge.state(), MotorState::Stop);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_a_rejected_brake_is_reported() {
+        let mut bridge = Bridge::new(pin(), pin());
+
+        bridge.set_brake_policy(BrakePolicy::Reject);
+
+        let mut bridge = bridge.into_infallible();
+
+        bridge.forward();
+
+        assert_eq!(bridge.stop(), Err(MotorDriverError::Unsupported));
+        assert_eq!(bridge.get_ref().state(), MotorState::Forward(100));
+    }
+
+    #[test]
+    fn test_split_and_parallel_drivers_borrow_infallible_views() {
+        let mut split = SplitDriver::new(pin(), pin(), pin(), pin());
+
+        split.a.infallible().forward();
+        split.b.infallible().reverse();
+
+        assert_eq!(split.states(), (MotorState::Forward(100), MotorState::Reverse(100)));
+
+        let mut parallel = ParallelDriver::new(pin(), pin(), pin(), pin());
+
+        parallel.infallible().forward();
+        parallel.infallible().coast();
+
+        assert_eq!(parallel.states(), (MotorState::Coast, MotorState::Coast));
+    }
+}
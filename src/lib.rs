@@ -18,11 +18,108 @@
 //!
 //! #### [`PWM Parallel`](MotorDriver::new_pwm_parallel)
 //! Controls both bridges simultaneously with a single PWM signal.
+//!
+//! #### [`PWM Parallel Single Owner`](MotorDriver::new_pwm_parallel_single_owner)
+//! Same as PWM Parallel, but the PWM is owned by the driver instead of being shared behind an `Arc<Mutex<_>>`.
+//!
+//! #### [`Mixed`](MotorDriver::new_mixed)
+//! Controls bridge A with PWM signals and bridge B with digital pins, for devices short of PWM channels.
+//!
+//! #### [`Stepper`](MotorDriver::new_stepper)
+//! Drives a bipolar stepper motor in full steps, with one coil on each bridge.
+//!
+//! ### Pin requirements
+//!
+//! Each mode only requires the [embedded-hal](embedded_hal) traits it actually uses:
+//!
+//! | Mode               | IN1..IN4                             | eep / sleep                              | fault                                  |
+//! |--------------------|--------------------------------------|------------------------------------------|----------------------------------------|
+//! | Split              | [`OutputPin`](embedded_hal::digital::OutputPin) | optional [`OutputPin`](embedded_hal::digital::OutputPin) | optional [`InputPin`](embedded_hal::digital::InputPin) |
+//! | Parallel           | [`OutputPin`](embedded_hal::digital::OutputPin) | optional [`OutputPin`](embedded_hal::digital::OutputPin) | optional [`InputPin`](embedded_hal::digital::InputPin) |
+//! | PWM Split          | [`SetDutyCycle`](embedded_hal::pwm::SetDutyCycle) | optional [`OutputPin`](embedded_hal::digital::OutputPin) | optional [`InputPin`](embedded_hal::digital::InputPin) |
+//! | PWM Split Single   | [`OutputPin`](embedded_hal::digital::OutputPin) | [`SetDutyCycle`](embedded_hal::pwm::SetDutyCycle) | optional [`InputPin`](embedded_hal::digital::InputPin) |
+//! | PWM Parallel       | [`OutputPin`](embedded_hal::digital::OutputPin) | [`SetDutyCycle`](embedded_hal::pwm::SetDutyCycle) | optional [`InputPin`](embedded_hal::digital::InputPin) |
+//! | Mixed              | IN1/IN2 [`SetDutyCycle`](embedded_hal::pwm::SetDutyCycle), IN3/IN4 [`OutputPin`](embedded_hal::digital::OutputPin) | optional [`OutputPin`](embedded_hal::digital::OutputPin) | optional [`InputPin`](embedded_hal::digital::InputPin) |
+//! | Stepper            | [`OutputPin`](embedded_hal::digital::OutputPin) | optional [`OutputPin`](embedded_hal::digital::OutputPin) | optional [`InputPin`](embedded_hal::digital::InputPin) |
+//!
+//! The constructor examples use [embedded-hal-mock](https://docs.rs/embedded-hal-mock) pins as stand-ins
+//! for the pins and PWM channels of your HAL, so they are compiled against the current API.
+//!
+//! ### Features
+//!
+//! - `critical-section-pwm`: adds `MotorDriver::new_pwm_parallel_critical_section`, a PWM parallel mode
+//!   sharing the PWM through a static `critical_section::Mutex<RefCell<_>>`, for interrupt driven access on bare metal.
+//! - `default-slow-decay`: PWM bridges default to [`Decay::Slow`] instead of [`Decay::Fast`].
+//! - `embassy-time`: adds async timed moves to [`PwmBridge`], e.g. `forward_for(50, Duration::from_millis(500)).await`,
+//!   waiting on an [embassy-time](https://docs.rs/embassy-time) timer instead of a delay provider.
+//! - `mock-delay`: exposes `MockDelay`, a delay provider recording the requested durations for tests.
+//! - `panic-on-invalid`: treats a speed percentage above 100 passed to `forward`, `reverse` or `set_duty_cycle`
+//!   as a programmer error, panicking in debug builds instead of returning [`MotorDriverError::InvalidRange`]
+//!   or clamping it. Release builds keep the default behavior.
+//! - `panic-stop`: exposes the `emergency` module, a [critical-section](https://docs.rs/critical-section) based
+//!   registry of statically stored motors which `emergency::all_stop` coasts, e.g. from a panic handler.
+//! - `no-fault`: compiles out the fault subsystem. [`MotorDriver`] no longer stores the fault pin and
+//!   `is_faulty` is removed. The `FAULT` generic is kept so constructor
+//!   signatures are unchanged, but it is no longer bound to [`InputPin`](embedded_hal::digital::InputPin),
+//!   so `None::<()>` can be passed instead of a pin type.
+//! - `std` (default): adds `MotorDriver::new_pwm_parallel`, sharing the PWM behind an `Arc<Mutex<_>>`, `SharedSleep`
+//!   and the [`std::error::Error`] implementations. Without it the crate is `no_std`, e.g. for bare metal targets
+//!   using `critical-section-pwm`.
+#![cfg_attr(not(any(test, feature = "std")), no_std)]
+mod any_driver;
 mod bridge;
+mod builder;
+mod coast;
+mod config;
 mod driver;
+#[cfg(any(test, feature = "panic-stop"))]
+pub mod emergency;
+mod fault_debouncer;
+mod infallible;
+mod mixed_driver;
+#[cfg(any(test, feature = "mock-delay"))]
+mod mock_delay;
+#[cfg(test)]
+mod mock_pins;
 mod parallel_driver;
+mod pid;
 mod split_driver;
+mod pwm_enable;
 mod pwm_parallel_driver;
 mod pwm_split_driver;
+mod retry;
+mod sequence;
+mod servo;
+#[cfg(feature = "std")]
+mod shared_sleep;
+mod soft_pwm;
+mod stepper;
+#[cfg(feature = "embassy-time")]
+mod timed;
+mod timing;
 
+pub use any_driver::AnyDriver;
+pub use bridge::{Bridge, PwmBridge, RoundMode, TORQUE_CURVE_POINTS};
+pub use builder::MotorDriverBuilder;
+pub use coast::CoastModel;
+pub use config::DriverConfig;
 pub use driver::*;
+pub use fault_debouncer::FaultDebouncer;
+pub use infallible::{InfallibleBridge, InfallibleMotor, InfalliblePins};
+#[cfg(feature = "mock-delay")]
+pub use mock_delay::MockDelay;
+pub use mixed_driver::MixedDriver;
+pub use parallel_driver::{Leg, ParallelDriver};
+pub use pid::{Pid, PID_GAIN_SCALE};
+pub use pwm_enable::{EnablingPwm, PwmEnable};
+pub use pwm_parallel_driver::{LockedPwmParallel, OwnedPwm, PwmAccess, PwmParallelDriver};
+pub use pwm_split_driver::{PwmChannelId, PwmSplitDriver};
+pub use retry::RetryPin;
+pub use sequence::{Sequence, Step};
+pub use servo::{PositionSensor, Servo};
+#[cfg(feature = "std")]
+pub use shared_sleep::SharedSleep;
+pub use soft_pwm::SoftPwmAdapter;
+pub use split_driver::SplitDriver;
+pub use stepper::StepperDriver;
+pub use timing::{Clock, CommandTiming, Instrumented};
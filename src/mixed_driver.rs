@@ -0,0 +1,127 @@
+use embedded_hal::digital::OutputPin;
+use embedded_hal::pwm::SetDutyCycle;
+
+use crate::bridge::{Bridge, PwmBridge};
+use crate::driver::{Breaks, BridgeStates, CurrentLoad, Driver, MotorDriverError, MotorState};
+
+/// Drives bridge A with PWM signals and bridge B with plain digital pins, for chips that are one or two
+/// PWM channels short of the PWM split mode.
+pub struct MixedDriver<IN1, IN2, IN3, IN4>
+    where
+        IN3: OutputPin,
+        IN4: OutputPin,
+{
+    pub a: PwmBridge<IN1, IN2>,
+    pub b: Bridge<IN3, IN4>,
+}
+
+impl<IN1, IN2, IN3, IN4> MixedDriver<IN1, IN2, IN3, IN4>
+    where
+        IN3: OutputPin,
+        IN4: OutputPin,
+{
+    pub fn new(in1: IN1, in2: IN2, in3: IN3, in4: IN4) -> Self {
+        Self {
+            a: PwmBridge::new(in1, in2, 0),
+            b: Bridge::new(in3, in4),
+        }
+    }
+}
+
+impl<IN1, IN2, IN3, IN4> Driver for MixedDriver<IN1, IN2, IN3, IN4>
+    where
+        IN3: OutputPin,
+        IN4: OutputPin,
+{}
+
+impl<IN1, IN2, IN3, IN4> Breaks for MixedDriver<IN1, IN2, IN3, IN4>
+    where
+        IN1: SetDutyCycle,
+        IN2: SetDutyCycle,
+        IN3: OutputPin,
+        IN4: OutputPin,
+{
+    fn coast(&mut self) -> Result<(), MotorDriverError> {
+        self.a.coast()?;
+        self.b.coast()?;
+
+        Ok(())
+    }
+
+    fn stop(&mut self) -> Result<(), MotorDriverError> {
+        self.a.stop()?;
+        self.b.stop()?;
+
+        Ok(())
+    }
+}
+
+impl<IN1, IN2, IN3, IN4> CurrentLoad for MixedDriver<IN1, IN2, IN3, IN4>
+    where
+        IN1: SetDutyCycle,
+        IN2: SetDutyCycle,
+        IN3: OutputPin,
+        IN4: OutputPin,
+{
+    fn load_percent(&self) -> u16 {
+        self.a.state().percent() as u16 + self.b.state().percent() as u16
+    }
+}
+
+impl<IN1, IN2, IN3, IN4> BridgeStates for MixedDriver<IN1, IN2, IN3, IN4>
+    where
+        IN1: SetDutyCycle,
+        IN2: SetDutyCycle,
+        IN3: OutputPin,
+        IN4: OutputPin,
+{
+    fn bridge_states(&self) -> [MotorState; 2] {
+        [self.a.state(), self.b.state()]
+    }
+
+    fn restore_bridge_states(&mut self, states: [MotorState; 2]) -> Result<(), MotorDriverError> {
+        self.a.set_state(states[0])?;
+        self.b.set_state(states[1])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use embedded_hal_mock::eh1::pin::Mock as Pin;
+    use embedded_hal_mock::eh1::pin::State::{High, Low};
+    use embedded_hal_mock::eh1::pin::Transaction;
+    use embedded_hal_mock::eh1::pwm::Mock as PwmPin;
+    use embedded_hal_mock::eh1::pwm::Transaction as PwmPinTransaction;
+
+    use crate::driver::{Breaks, Decay, MotorDriver, MotorDriverError, Movement, PwmMovement};
+
+    #[test]
+    fn test_one_bridge_is_speed_controlled_and_the_other_on_off() -> Result<(), MotorDriverError> {
+        let mut in1 = PwmPin::new(&[
+            PwmPinTransaction::max_duty_cycle(100),
+            PwmPinTransaction::set_duty_cycle(50),
+            PwmPinTransaction::set_duty_cycle(0),
+        ]);
+
+        let mut in2 = PwmPin::new(&[PwmPinTransaction::set_duty_cycle(0), PwmPinTransaction::set_duty_cycle(0)]);
+        let mut in3 = Pin::new(&[Transaction::set(Low), Transaction::set(Low)]);
+        let mut in4 = Pin::new(&[Transaction::set(High), Transaction::set(Low)]);
+
+        let mut motor = MotorDriver::new_mixed(
+            in1.clone(), in2.clone(), in3.clone(), in4.clone(), None::<Pin>, None::<Pin>,
+        );
+
+        motor.a.set_decay(Decay::Fast);
+
+        motor.a.forward(50)?;
+        motor.b.reverse()?;
+        motor.coast()?;
+
+        in1.done();
+        in2.done();
+        in3.done();
+        in4.done();
+
+        Ok(())
+    }
+}
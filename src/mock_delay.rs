@@ -0,0 +1,102 @@
+use core::time::Duration;
+
+use embedded_hal::delay::DelayNs;
+
+/// A delay provider which returns immediately and records every requested duration, so tests can
+/// assert the exact timing sequence produced by a command.
+///
+/// Available with the `mock-delay` feature.
+///
+/// # Example
+///
+/// ```ignore
+/// let mut delay = MockDelay::new();
+///
+/// CoastModel::new(2000).coast_and_wait(&mut motor.a, 3000, &mut delay)?;
+///
+/// assert_eq!(delay.delays(), &[Duration::from_millis(1500)]);
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct MockDelay {
+    delays: Vec<Duration>,
+}
+
+impl MockDelay {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the requested durations, in the order they were requested.
+    pub fn delays(&self) -> &[Duration] {
+        &self.delays
+    }
+
+    /// Returns the sum of all requested durations.
+    pub fn total(&self) -> Duration {
+        self.delays.iter().sum()
+    }
+
+    /// Discards the recorded durations.
+    pub fn clear(&mut self) {
+        self.delays.clear();
+    }
+}
+
+impl DelayNs for MockDelay {
+    fn delay_ns(&mut self, ns: u32) {
+        self.delays.push(Duration::from_nanos(ns as u64));
+    }
+
+    fn delay_us(&mut self, us: u32) {
+        self.delays.push(Duration::from_micros(us as u64));
+    }
+
+    fn delay_ms(&mut self, ms: u32) {
+        self.delays.push(Duration::from_millis(ms as u64));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use embedded_hal_mock::eh1::pin::Mock as Pin;
+    use embedded_hal_mock::eh1::pin::State::Low;
+    use embedded_hal_mock::eh1::pin::Transaction;
+
+    use crate::coast::CoastModel;
+    use crate::driver::{MotorDriver, MotorDriverError};
+    use crate::mock_delay::MockDelay;
+
+    #[test]
+    fn test_records_each_requested_delay() -> Result<(), MotorDriverError> {
+        let mut in1 = Pin::new(&[Transaction::set(Low), Transaction::set(Low)]);
+        let mut in2 = Pin::new(&[Transaction::set(Low), Transaction::set(Low)]);
+        let mut in3 = Pin::new(&[]);
+        let mut in4 = Pin::new(&[]);
+
+        let mut motor = MotorDriver::new_split(
+            in1.clone(), in2.clone(), in3.clone(), in4.clone(), None::<Pin>, None::<Pin>,
+        );
+
+        let model = CoastModel::new(2000);
+        let mut delay = MockDelay::new();
+
+        model.coast_and_wait(&mut motor.a, 3000, &mut delay)?;
+        model.coast_and_wait(&mut motor.a, 1000, &mut delay)?;
+
+        assert_eq!(delay.delays(), &[Duration::from_millis(1500), Duration::from_millis(500)]);
+        assert_eq!(delay.total(), Duration::from_millis(2000));
+
+        delay.clear();
+
+        assert!(delay.delays().is_empty());
+
+        in1.done();
+        in2.done();
+        in3.done();
+        in4.done();
+
+        Ok(())
+    }
+}
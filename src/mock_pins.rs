@@ -0,0 +1,74 @@
+use embedded_hal_mock::eh1::pin::{Mock as Pin, State, Transaction};
+
+/// The full set of pin mocks of a DRV8833, built from the levels each pin is expected to see, so
+/// tests don't have to spell out every transaction.
+///
+/// ```ignore
+/// let mut pins = MockPins::new([&[High], &[Low], &[], &[]]).with_sleep(&[High]);
+/// let [in1, in2, in3, in4] = pins.ins();
+///
+/// let mut motor = MotorDriver::new_split(in1, in2, in3, in4, Some(pins.sleep()), Some(pins.fault()));
+///
+/// motor.wakeup()?;
+/// motor.a.forward()?;
+///
+/// pins.done();
+/// ```
+pub(crate) struct MockPins {
+    ins: [Pin; 4],
+    sleep: Pin,
+    fault: Pin,
+}
+
+impl MockPins {
+    /// Expects each level to be set on IN1 to IN4 in order, with no activity on sleep or fault.
+    pub(crate) fn new(ins: [&[State]; 4]) -> Self {
+        Self {
+            ins: ins.map(|levels| Pin::new(&levels.iter().map(|level| Transaction::set(*level)).collect::<Vec<_>>())),
+            sleep: Pin::new(&[]),
+            fault: Pin::new(&[]),
+        }
+    }
+
+    /// Expects each level to be set on the sleep pin in order.
+    pub(crate) fn with_sleep(mut self, levels: &[State]) -> Self {
+        let sleep = Pin::new(&levels.iter().map(|level| Transaction::set(*level)).collect::<Vec<_>>());
+
+        std::mem::replace(&mut self.sleep, sleep).done();
+
+        self
+    }
+
+    /// Expects the fault pin to be read once per level, returning each level in order.
+    #[cfg_attr(feature = "no-fault", allow(dead_code))]
+    pub(crate) fn with_fault(mut self, levels: &[State]) -> Self {
+        let fault = Pin::new(&levels.iter().map(|level| Transaction::get(*level)).collect::<Vec<_>>());
+
+        std::mem::replace(&mut self.fault, fault).done();
+
+        self
+    }
+
+    pub(crate) fn ins(&self) -> [Pin; 4] {
+        self.ins.clone()
+    }
+
+    pub(crate) fn sleep(&self) -> Pin {
+        self.sleep.clone()
+    }
+
+    #[cfg_attr(feature = "no-fault", allow(dead_code))]
+    pub(crate) fn fault(&self) -> Pin {
+        self.fault.clone()
+    }
+
+    /// Asserts every expected transaction happened on every pin.
+    pub(crate) fn done(&mut self) {
+        for pin in self.ins.iter_mut() {
+            pin.done();
+        }
+
+        self.sleep.done();
+        self.fault.done();
+    }
+}
@@ -1,8 +1,20 @@
-use embedded_hal::digital::OutputPin;
+use embedded_hal::digital::{OutputPin, PinState};
 
 use crate::bridge::Bridge;
-use crate::driver::{Breaks, Driver, MotorDriverError, Movement};
+use crate::driver::{check_percent, BrakePolicy, Breaks, CurrentLoad, Direction, Driver, MotorDriverError, MotorState, Movement};
 
+/// One of the two paralleled legs, leg A being IN1/IN2 and leg B IN3/IN4.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Leg {
+    A,
+    B,
+}
+
+/// Drives a single motor from both bridges, with IN1/IN3 and IN2/IN4 paralleled for twice the current.
+///
+/// There is no `forward_both` as in split mode: [Movement::forward] and [Movement::reverse] always
+/// command both legs together, and nothing short of [ParallelDriver::set_legs] or [ParallelDriver::drive_leg]
+/// addresses a single leg.
 pub struct ParallelDriver<IN1, IN2, IN3, IN4>
     where
         IN1: OutputPin,
@@ -12,6 +24,8 @@ pub struct ParallelDriver<IN1, IN2, IN3, IN4>
 {
     a: Bridge<IN1, IN2>,
     b: Bridge<IN3, IN4>,
+    fault_tolerant: bool,
+    degraded: bool,
 }
 
 impl<IN1, IN2, IN3, IN4> Driver for ParallelDriver<IN1, IN2, IN3, IN4>
@@ -30,17 +44,27 @@ impl<IN1, IN2, IN3, IN4> Movement for ParallelDriver<IN1, IN2, IN3, IN4>
         IN4: OutputPin,
 {
     fn forward(&mut self) -> Result<(), MotorDriverError> {
-        self.a.forward()?;
-        self.b.forward()?;
+        match self.fault_tolerant {
+            true => {
+                let a = self.a.forward();
+                let b = self.b.forward();
 
-        Ok(())
+                self.tolerate(a, b)
+            }
+            false => self.drive_both(Direction::Forward),
+        }
     }
 
     fn reverse(&mut self) -> Result<(), MotorDriverError> {
-        self.a.reverse()?;
-        self.b.reverse()?;
+        match self.fault_tolerant {
+            true => {
+                let a = self.a.reverse();
+                let b = self.b.reverse();
 
-        Ok(())
+                self.tolerate(a, b)
+            }
+            false => self.drive_both(Direction::Reverse),
+        }
     }
 }
 
@@ -52,17 +76,81 @@ impl<IN1, IN2, IN3, IN4> Breaks for ParallelDriver<IN1, IN2, IN3, IN4>
         IN4: OutputPin,
 {
     fn coast(&mut self) -> Result<(), MotorDriverError> {
-        self.a.coast()?;
-        self.b.coast()?;
+        match self.fault_tolerant {
+            true => {
+                let a = self.a.coast();
+                let b = self.b.coast();
 
-        Ok(())
+                self.tolerate(a, b)
+            }
+            false => {
+                self.a.coast()?;
+                self.b.coast()
+            }
+        }
     }
 
     fn stop(&mut self) -> Result<(), MotorDriverError> {
-        self.a.stop()?;
-        self.b.stop()?;
+        match self.fault_tolerant {
+            true => {
+                let a = self.a.stop();
+                let b = self.b.stop();
 
-        Ok(())
+                self.tolerate(a, b)
+            }
+            false => {
+                self.a.stop()?;
+                self.b.stop()
+            }
+        }
+    }
+}
+
+impl<IN1, IN2, IN3, IN4> CurrentLoad for ParallelDriver<IN1, IN2, IN3, IN4>
+    where
+        IN1: OutputPin,
+        IN2: OutputPin,
+        IN3: OutputPin,
+        IN4: OutputPin,
+{
+    fn load_percent(&self) -> u16 {
+        2 * self.a.state().percent() as u16
+    }
+}
+
+impl<IN1, IN2, IN3, IN4> crate::driver::DynMotor for ParallelDriver<IN1, IN2, IN3, IN4>
+    where
+        IN1: OutputPin,
+        IN2: OutputPin,
+        IN3: OutputPin,
+        IN4: OutputPin,
+{
+    fn forward(&mut self, percent: u8) -> Result<(), MotorDriverError> {
+        check_percent(percent);
+
+        match percent {
+            0 => Breaks::coast(self),
+            1..=100 => Movement::forward(self),
+            _ => Err(MotorDriverError::InvalidRange),
+        }
+    }
+
+    fn reverse(&mut self, percent: u8) -> Result<(), MotorDriverError> {
+        check_percent(percent);
+
+        match percent {
+            0 => Breaks::coast(self),
+            1..=100 => Movement::reverse(self),
+            _ => Err(MotorDriverError::InvalidRange),
+        }
+    }
+
+    fn coast(&mut self) -> Result<(), MotorDriverError> {
+        Breaks::coast(self)
+    }
+
+    fn stop(&mut self) -> Result<(), MotorDriverError> {
+        Breaks::stop(self)
     }
 }
 
@@ -77,16 +165,196 @@ impl<IN1, IN2, IN3, IN4> ParallelDriver<IN1, IN2, IN3, IN4>
         Self {
             a: Bridge::new(in1, in2),
             b: Bridge::new(in3, in4),
+            fault_tolerant: false,
+            degraded: false,
+        }
+    }
+
+    /// When enabled, a command failing on a single leg, e.g. due to a bad solder joint on IN3, is no
+    /// longer reported as an error: the failed leg is coasted while the healthy one keeps driving at
+    /// reduced current, and the driver is flagged as [degraded](ParallelDriver::degraded). Commands
+    /// still fail when both legs fail.
+    pub fn set_fault_tolerant(&mut self, fault_tolerant: bool) {
+        self.fault_tolerant = fault_tolerant;
+    }
+
+    /// Returns whether a leg failed while running in [fault tolerant](ParallelDriver::set_fault_tolerant)
+    /// mode. The flag is kept until [ParallelDriver::clear_degraded] is called.
+    pub fn degraded(&self) -> bool {
+        self.degraded
+    }
+
+    pub fn clear_degraded(&mut self) {
+        self.degraded = false;
+    }
+
+    /// Sets the [BrakePolicy] of both legs, see [Bridge::set_brake_policy].
+    pub fn set_brake_policy(&mut self, policy: BrakePolicy) {
+        self.a.set_brake_policy(policy);
+        self.b.set_brake_policy(policy);
+    }
+
+    /// Commands each leg separately, e.g. to check a single leg by driving it while the other coasts.
+    ///
+    /// Returns [MotorDriverError::ConflictingLegs] without touching the pins unless the legs are commanded
+    /// into the same state or one of them coasts, as the paralleled outputs would otherwise drive against
+    /// each other. The legs are written in the order that avoids such a state in between, coasting leg B
+    /// first when neither order does.
+    #[must_use = "motor command result must be checked"]
+    pub fn set_legs(&mut self, a: MotorState, b: MotorState) -> Result<(), MotorDriverError> {
+        if !Self::legs_agree(a, b) {
+            return Err(MotorDriverError::ConflictingLegs);
+        }
+
+        if !Self::legs_agree(a, self.b.state()) && !Self::legs_agree(self.a.state(), b) {
+            Breaks::coast(&mut self.b)?;
+        }
+
+        match Self::legs_agree(a, self.b.state()) {
+            true => {
+                self.a.set_state(a)?;
+                self.b.set_state(b)
+            }
+            false => {
+                self.b.set_state(b)?;
+                self.a.set_state(a)
+            }
+        }
+    }
+
+    /// Drives a single leg in the given direction while coasting the other one, e.g. to find a bad FET
+    /// during bring-up by checking each leg separately. This breaks the lockstep of the paralleled legs
+    /// until the next command addressing both of them.
+    #[must_use = "motor command result must be checked"]
+    pub fn drive_leg(&mut self, leg: Leg, direction: Direction) -> Result<(), MotorDriverError> {
+        let state = match direction {
+            Direction::Forward => MotorState::Forward(100),
+            Direction::Reverse => MotorState::Reverse(100),
+        };
+
+        match leg {
+            Leg::A => self.set_legs(state, MotorState::Coast),
+            Leg::B => self.set_legs(MotorState::Coast, state),
+        }
+    }
+
+    /// Whether two leg states can be applied at the same time, which is when either leg is in Hi-Z or both
+    /// drive the outputs to the same levels.
+    fn legs_agree(a: MotorState, b: MotorState) -> bool {
+        a == MotorState::Coast || b == MotorState::Coast || a.pin_states() == b.pin_states()
+    }
+
+    /// Returns the two paralleled legs, for diagnostics. Driving them independently leaves the legs
+    /// fighting each other, so this is not meant for general use, see [ParallelDriver::set_legs] instead.
+    #[doc(hidden)]
+    pub fn bridges_mut(&mut self) -> (&mut Bridge<IN1, IN2>, &mut Bridge<IN3, IN4>) {
+        (&mut self.a, &mut self.b)
+    }
+
+    /// Combines the results of a command issued to each leg in fault tolerant mode, where a single
+    /// failed leg flags the driver as degraded instead of failing the command.
+    fn tolerate(&mut self, a: Result<(), MotorDriverError>, b: Result<(), MotorDriverError>) -> Result<(), MotorDriverError> {
+        match (a, b) {
+            (Ok(()), Ok(())) => Ok(()),
+            (Err(error), Err(_)) => Err(error),
+            _ => {
+                self.degraded = true;
+
+                Ok(())
+            }
+        }
+    }
+
+    /// Drives both legs in the given direction, writing the pins driven high on both bridges first and
+    /// then the pins driven low, e.g. IN1, IN3, IN2 and IN4 when going forward. Grouping same-level
+    /// writes keeps the paralleled legs in agreement for all but the write between each pair, instead
+    /// of one leg lagging a whole bridge behind the other.
+    ///
+    /// If any write fails both bridges are coasted, so the legs are never left fighting each other.
+    fn drive_both(&mut self, direction: Direction) -> Result<(), MotorDriverError> {
+        let result = match direction {
+            Direction::Forward => self
+                .a
+                .set_in1(PinState::High)
+                .and_then(|_| self.b.set_in1(PinState::High))
+                .and_then(|_| self.a.set_in2(PinState::Low))
+                .and_then(|_| self.b.set_in2(PinState::Low)),
+            Direction::Reverse => self
+                .a
+                .set_in2(PinState::High)
+                .and_then(|_| self.b.set_in2(PinState::High))
+                .and_then(|_| self.a.set_in1(PinState::Low))
+                .and_then(|_| self.b.set_in1(PinState::Low)),
+        };
+
+        if let Err(error) = result {
+            let _ = Breaks::coast(&mut self.a);
+            let _ = Breaks::coast(&mut self.b);
+
+            return Err(error);
         }
+
+        let state = match direction {
+            Direction::Forward => MotorState::Forward(100),
+            Direction::Reverse => MotorState::Reverse(100),
+        };
+
+        self.a.record_state(state);
+        self.b.record_state(state);
+
+        Ok(())
+    }
+
+    /// Returns the levels last written to IN1, IN2, IN3 and IN4, derived from each bridge's tracked state
+    /// rather than read back from the pins.
+    pub fn pin_states(&self) -> [PinState; 4] {
+        let [in1, in2] = self.a.state().pin_states();
+        let [in3, in4] = self.b.state().pin_states();
+
+        [in1, in2, in3, in4]
+    }
+
+    /// Returns the last state successfully commanded to bridge A and B, e.g. to log both at once.
+    pub fn states(&self) -> (MotorState, MotorState) {
+        (self.a.state(), self.b.state())
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use std::cell::RefCell;
+    use std::convert::Infallible;
+    use std::io::ErrorKind;
+    use std::rc::Rc;
+
     use embedded_hal_mock::eh1::pin::State::{High, Low};
+    use embedded_hal::digital::{ErrorType, OutputPin, PinState};
     use embedded_hal_mock::eh1::pin::{Mock as Pin, Transaction};
+    use embedded_hal_mock::eh1::MockError;
 
-    use crate::driver::{Breaks, MotorDriver, MotorDriverError, Movement};
+    use crate::driver::{Breaks, Direction, MotorDriver, MotorDriverError, MotorState, Movement};
+    use crate::parallel_driver::Leg;
+
+    /// Records every write into a log shared by all pins, to assert the order across pins.
+    struct LoggedPin(u8, Rc<RefCell<Vec<(u8, PinState)>>>);
+
+    impl ErrorType for LoggedPin {
+        type Error = Infallible;
+    }
+
+    impl OutputPin for LoggedPin {
+        fn set_low(&mut self) -> Result<(), Self::Error> {
+            self.1.borrow_mut().push((self.0, PinState::Low));
+
+            Ok(())
+        }
+
+        fn set_high(&mut self) -> Result<(), Self::Error> {
+            self.1.borrow_mut().push((self.0, PinState::High));
+
+            Ok(())
+        }
+    }
 
     #[test]
     fn test_all_operation_are_driven_simultaneously() -> Result<(), MotorDriverError> {
@@ -139,4 +407,218 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_pin_states_reflect_the_last_command() -> Result<(), MotorDriverError> {
+        let mut in1 = Pin::new(&[Transaction::set(High)]);
+        let mut in2 = Pin::new(&[Transaction::set(Low)]);
+        let mut in3 = Pin::new(&[Transaction::set(High)]);
+        let mut in4 = Pin::new(&[Transaction::set(Low)]);
+
+        let mut motor = MotorDriver::new_parallel(
+            in1.clone(), in2.clone(), in3.clone(), in4.clone(), None::<Pin>, None::<Pin>,
+        );
+
+        assert_eq!(motor.pin_states(), [PinState::Low; 4]);
+
+        motor.forward()?;
+
+        assert_eq!(motor.pin_states(), [PinState::High, PinState::Low, PinState::High, PinState::Low]);
+
+        in1.done();
+        in2.done();
+        in3.done();
+        in4.done();
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_legs_are_reachable_for_diagnostics() -> Result<(), MotorDriverError> {
+        let mut in1 = Pin::new(&[Transaction::set(High)]);
+        let mut in2 = Pin::new(&[Transaction::set(Low)]);
+        let mut in3 = Pin::new(&[Transaction::set(Low)]);
+        let mut in4 = Pin::new(&[Transaction::set(Low)]);
+
+        let mut motor = MotorDriver::new_parallel(
+            in1.clone(), in2.clone(), in3.clone(), in4.clone(), None::<Pin>, None::<Pin>,
+        );
+
+        let (a, b) = motor.bridges_mut();
+
+        a.forward()?;
+        b.coast()?;
+
+        assert_eq!(a.state(), MotorState::Forward(100));
+        assert_eq!(b.state(), MotorState::Coast);
+
+        in1.done();
+        in2.done();
+        in3.done();
+        in4.done();
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_a_failing_leg_degrades_instead_of_failing_in_fault_tolerant_mode() -> Result<(), MotorDriverError> {
+        let mut in1 = Pin::new(&[Transaction::set(High)]);
+        let mut in2 = Pin::new(&[Transaction::set(Low)]);
+        let mut in3 = Pin::new(&[Transaction::set(High).with_error(MockError::Io(ErrorKind::Other)), Transaction::set(Low)]);
+        let mut in4 = Pin::new(&[Transaction::set(Low)]);
+
+        let mut motor = MotorDriver::new_parallel(
+            in1.clone(), in2.clone(), in3.clone(), in4.clone(), None::<Pin>, None::<Pin>,
+        );
+
+        motor.set_fault_tolerant(true);
+        motor.forward()?;
+
+        assert!(motor.degraded());
+
+        let (a, b) = motor.bridges_mut();
+
+        assert_eq!(a.state(), MotorState::Forward(100));
+        assert_eq!(b.state(), MotorState::Coast);
+
+        motor.clear_degraded();
+
+        assert!(!motor.degraded());
+
+        in1.done();
+        in2.done();
+        in3.done();
+        in4.done();
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_same_level_writes_are_grouped_across_bridges() -> Result<(), MotorDriverError> {
+        let log = Rc::new(RefCell::new(Vec::new()));
+
+        let mut motor = MotorDriver::new_parallel(
+            LoggedPin(1, log.clone()),
+            LoggedPin(2, log.clone()),
+            LoggedPin(3, log.clone()),
+            LoggedPin(4, log.clone()),
+            None::<Pin>,
+            None::<Pin>,
+        );
+
+        motor.forward()?;
+
+        assert_eq!(
+            log.take(),
+            [(1, PinState::High), (3, PinState::High), (2, PinState::Low), (4, PinState::Low)]
+        );
+
+        motor.reverse()?;
+
+        assert_eq!(
+            log.take(),
+            [(2, PinState::High), (4, PinState::High), (1, PinState::Low), (3, PinState::Low)]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_legs_cannot_be_commanded_against_each_other() -> Result<(), MotorDriverError> {
+        let log = Rc::new(RefCell::new(Vec::new()));
+
+        let mut motor = MotorDriver::new_parallel(
+            LoggedPin(1, log.clone()),
+            LoggedPin(2, log.clone()),
+            LoggedPin(3, log.clone()),
+            LoggedPin(4, log.clone()),
+            None::<Pin>,
+            None::<Pin>,
+        );
+
+        motor.forward()?;
+        log.take();
+
+        assert!(matches!(motor.set_legs(MotorState::Reverse(100), MotorState::Forward(100)), Err(MotorDriverError::ConflictingLegs)));
+        assert!(matches!(motor.set_legs(MotorState::Stop, MotorState::Forward(100)), Err(MotorDriverError::ConflictingLegs)));
+        assert!(log.borrow().is_empty());
+
+        motor.set_legs(MotorState::Reverse(100), MotorState::Reverse(100))?;
+
+        assert_eq!(
+            log.take(),
+            [
+                (3, PinState::Low),
+                (4, PinState::Low),
+                (1, PinState::Low),
+                (2, PinState::High),
+                (3, PinState::Low),
+                (4, PinState::High),
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_a_single_leg_is_driven_for_diagnostics() -> Result<(), MotorDriverError> {
+        let log = Rc::new(RefCell::new(Vec::new()));
+
+        let mut motor = MotorDriver::new_parallel(
+            LoggedPin(1, log.clone()),
+            LoggedPin(2, log.clone()),
+            LoggedPin(3, log.clone()),
+            LoggedPin(4, log.clone()),
+            None::<Pin>,
+            None::<Pin>,
+        );
+
+        motor.drive_leg(Leg::A, Direction::Forward)?;
+
+        assert_eq!(motor.pin_states(), [PinState::High, PinState::Low, PinState::Low, PinState::Low]);
+        assert_eq!(motor.states(), (MotorState::Forward(100), MotorState::Coast));
+        assert!(log.take().iter().all(|(pin, state)| *pin <= 2 || *state == PinState::Low));
+
+        motor.drive_leg(Leg::B, Direction::Reverse)?;
+
+        assert_eq!(motor.pin_states(), [PinState::Low, PinState::Low, PinState::Low, PinState::High]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_current_estimate_doubles_in_parallel_mode() -> Result<(), MotorDriverError> {
+        let mut in1 = Pin::new(&[Transaction::set(High), Transaction::set(High)]);
+        let mut in2 = Pin::new(&[Transaction::set(Low), Transaction::set(Low)]);
+        let mut in3 = Pin::new(&[Transaction::set(High)]);
+        let mut in4 = Pin::new(&[Transaction::set(Low)]);
+
+        let mut split = MotorDriver::new_split(
+            in1.clone(), in2.clone(), in3.clone(), in4.clone(), None::<Pin>, None::<Pin>,
+        );
+
+        split.set_stall_current_ma(1200);
+
+        assert_eq!(split.estimated_current_ma(), 0);
+
+        split.a.forward()?;
+
+        assert_eq!(split.estimated_current_ma(), 1200);
+
+        let mut parallel = MotorDriver::new_parallel(
+            in1.clone(), in2.clone(), in3.clone(), in4.clone(), None::<Pin>, None::<Pin>,
+        );
+
+        parallel.set_stall_current_ma(1200);
+        parallel.forward()?;
+
+        assert_eq!(parallel.estimated_current_ma(), 2400);
+
+        in1.done();
+        in2.done();
+        in3.done();
+        in4.done();
+
+        Ok(())
+    }
 }
@@ -0,0 +1,102 @@
+/// The fixed-point scale of the [Pid] gains, a gain of `100` contributes 1% of output per unit.
+pub const PID_GAIN_SCALE: i32 = 100;
+
+/// An integer PID controller producing a signed speed percentage from a position error, e.g. for a
+/// [Servo](crate::Servo).
+///
+/// Gains are fixed-point in hundredths, see [PID_GAIN_SCALE], so `Pid::new(250, 5, 0)` applies 2.5% per
+/// unit of error plus 0.05% per unit of accumulated error. The output is clamped to `-100..=100`, and
+/// the integral only accumulates while that doesn't push the output further into the clamp, so it can't
+/// wind up while the motor is saturated and overshoot once the error finally shrinks.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pid {
+    kp: u16,
+    ki: u16,
+    kd: u16,
+    integral: i32,
+    last_error: Option<i32>,
+}
+
+impl Pid {
+    pub fn new(kp: u16, ki: u16, kd: u16) -> Self {
+        Self {
+            kp,
+            ki,
+            kd,
+            integral: 0,
+            last_error: None,
+        }
+    }
+
+    /// Replaces the gains, keeping the accumulated integral.
+    pub fn set_gains(&mut self, kp: u16, ki: u16, kd: u16) {
+        self.kp = kp;
+        self.ki = ki;
+        self.kd = kd;
+    }
+
+    /// Forgets the accumulated integral and the last error, e.g. when the target changes.
+    pub fn reset(&mut self) {
+        self.integral = 0;
+        self.last_error = None;
+    }
+
+    /// Runs a single step of the controller and returns the output as a signed percentage, positive
+    /// to reduce a positive error.
+    pub fn update(&mut self, error: i32) -> i8 {
+        let derivative = self.last_error.map_or(0, |last| error.saturating_sub(last));
+
+        self.last_error = Some(error);
+
+        let integral = self.integral.saturating_add(error);
+        let unclamped = self.output(error, integral, derivative);
+
+        if unclamped.abs() <= 100 || unclamped.signum() != error.signum() {
+            self.integral = integral;
+        }
+
+        self.output(error, self.integral, derivative).clamp(-100, 100) as i8
+    }
+
+    fn output(&self, error: i32, integral: i32, derivative: i32) -> i32 {
+        let p = (self.kp as i32).saturating_mul(error);
+        let i = (self.ki as i32).saturating_mul(integral);
+        let d = (self.kd as i32).saturating_mul(derivative);
+
+        p.saturating_add(i).saturating_add(d) / PID_GAIN_SCALE
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::pid::Pid;
+
+    #[test]
+    fn test_integral_removes_steady_state_error_without_winding_up() {
+        let mut pid = Pid::new(100, 50, 0);
+
+        assert_eq!(pid.update(4), 6);
+        assert_eq!(pid.update(4), 8);
+        assert_eq!(pid.update(4), 10);
+
+        for _ in 0..1000 {
+            assert_eq!(pid.update(90), 96);
+        }
+
+        assert!(pid.update(-10) < 0);
+    }
+
+    #[test]
+    fn test_large_errors_saturate_instead_of_overflowing() {
+        let mut pid = Pid::new(u16::MAX, 0, 0);
+
+        assert_eq!(pid.update(40_000), 100);
+        assert_eq!(pid.update(-40_000), -100);
+
+        let mut pid = Pid::new(0, 0, u16::MAX);
+
+        pid.update(i32::MAX);
+
+        assert_eq!(pid.update(i32::MIN), -100);
+    }
+}
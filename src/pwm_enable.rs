@@ -0,0 +1,152 @@
+use embedded_hal::pwm::{ErrorType, SetDutyCycle};
+
+/// Implemented by PWM channels whose output must be enabled explicitly, as setting the duty alone
+/// doesn't start the output on some HALs.
+pub trait PwmEnable: ErrorType {
+    fn enable(&mut self) -> Result<(), Self::Error>;
+
+    fn disable(&mut self) -> Result<(), Self::Error>;
+}
+
+/// Wraps a PWM channel implementing [PwmEnable] so it can be used by the PWM modes: the channel is
+/// disabled whenever its duty is set to zero, e.g. when coasting, and enabled again as soon as a
+/// non-zero duty is set, e.g. when driving forward.
+///
+/// # Example
+///
+/// ```ignore
+/// let mut motor = MotorDriver::new_pwm_split(
+///     EnablingPwm::new(pwm1), EnablingPwm::new(pwm2), EnablingPwm::new(pwm3), EnablingPwm::new(pwm4),
+///     None::<Pin>, None::<Pin>,
+/// );
+/// ```
+pub struct EnablingPwm<PWM> {
+    pwm: PWM,
+    enabled: Option<bool>,
+}
+
+impl<PWM> EnablingPwm<PWM> {
+    /// Creates a new wrapper. As the initial state of the channel is unknown, the first duty written
+    /// always enables or disables it.
+    pub fn new(pwm: PWM) -> Self {
+        Self { pwm, enabled: None }
+    }
+
+    /// Releases the wrapped channel.
+    pub fn release(self) -> PWM {
+        self.pwm
+    }
+}
+
+impl<PWM: ErrorType> ErrorType for EnablingPwm<PWM> {
+    type Error = PWM::Error;
+}
+
+impl<PWM: SetDutyCycle + PwmEnable> SetDutyCycle for EnablingPwm<PWM> {
+    fn max_duty_cycle(&self) -> u16 {
+        self.pwm.max_duty_cycle()
+    }
+
+    fn set_duty_cycle(&mut self, duty: u16) -> Result<(), Self::Error> {
+        let enable = duty > 0;
+
+        if enable {
+            self.pwm.set_duty_cycle(duty)?;
+        }
+
+        if self.enabled != Some(enable) {
+            match enable {
+                true => self.pwm.enable()?,
+                false => self.pwm.disable()?,
+            }
+
+            self.enabled = Some(enable);
+        }
+
+        if !enable {
+            self.pwm.set_duty_cycle(duty)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::Infallible;
+
+    use embedded_hal::pwm::{ErrorType, SetDutyCycle};
+
+    use crate::bridge::PwmBridge;
+    use crate::driver::{Breaks, Decay, MotorDriverError, PwmMovement};
+    use crate::pwm_enable::{EnablingPwm, PwmEnable};
+
+    #[derive(Debug, PartialEq)]
+    enum Call {
+        Duty(u16),
+        Enable,
+        Disable,
+    }
+
+    #[derive(Default)]
+    struct Channel(Vec<Call>);
+
+    impl ErrorType for Channel {
+        type Error = Infallible;
+    }
+
+    impl SetDutyCycle for Channel {
+        fn max_duty_cycle(&self) -> u16 {
+            100
+        }
+
+        fn set_duty_cycle(&mut self, duty: u16) -> Result<(), Self::Error> {
+            self.0.push(Call::Duty(duty));
+
+            Ok(())
+        }
+    }
+
+    impl PwmEnable for Channel {
+        fn enable(&mut self) -> Result<(), Self::Error> {
+            self.0.push(Call::Enable);
+
+            Ok(())
+        }
+
+        fn disable(&mut self) -> Result<(), Self::Error> {
+            self.0.push(Call::Disable);
+
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_coasting_disables_and_forward_enables_the_channel() -> Result<(), MotorDriverError> {
+        let mut bridge = PwmBridge::new(EnablingPwm::new(Channel::default()), EnablingPwm::new(Channel::default()), 0);
+
+        bridge.set_decay(Decay::Fast);
+
+        bridge.forward(50)?;
+        bridge.forward(80)?;
+        bridge.coast()?;
+        bridge.forward(50)?;
+
+        let (in1, _) = bridge.replace_pins(EnablingPwm::new(Channel::default()), EnablingPwm::new(Channel::default()));
+
+        assert_eq!(
+            in1.release().0,
+            [
+                Call::Duty(50),
+                Call::Enable,
+                Call::Duty(80),
+                Call::Disable,
+                Call::Duty(0),
+                Call::Duty(50),
+                Call::Enable,
+            ]
+        );
+
+        Ok(())
+    }
+}
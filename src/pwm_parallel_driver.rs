@@ -1,10 +1,13 @@
+#[cfg(any(test, feature = "critical-section-pwm"))]
+use core::cell::RefCell;
+#[cfg(feature = "std")]
 use std::sync::{Arc, Mutex};
 
 use embedded_hal::digital::OutputPin;
 use embedded_hal::pwm::SetDutyCycle;
 
-use crate::bridge::remap;
-use crate::driver::{Breaks, Driver, MotorDriverError, Movement, PwmMovement};
+use crate::bridge::{check_min_duty, remap, Bridge, RoundMode};
+use crate::driver::{check_percent, BrakePolicy, Breaks, CurrentLoad, Driver, MotorDriverError, Movement, PwmMovement};
 use crate::split_driver::SplitDriver;
 
 pub struct PwmParallelDriver<IN1, IN2, IN3, IN4, PWM>
@@ -17,6 +20,9 @@ pub struct PwmParallelDriver<IN1, IN2, IN3, IN4, PWM>
     pwm: PWM,
     split: SplitDriver<IN1, IN2, IN3, IN4>,
     min_duty: u16,
+    max_duty: Option<u16>,
+    round: RoundMode,
+    duty: u8,
 }
 
 impl<IN1, IN2, IN3, IN4, PWM> PwmParallelDriver<IN1, IN2, IN3, IN4, PWM>
@@ -30,61 +36,373 @@ impl<IN1, IN2, IN3, IN4, PWM> PwmParallelDriver<IN1, IN2, IN3, IN4, PWM>
         Self {
             pwm,
             min_duty: 0,
+            max_duty: None,
+            round: RoundMode::default(),
+            duty: 0,
             split: SplitDriver::new(in1, in2, in3, in4),
         }
     }
+
+    /// Returns the two paralleled legs, for diagnostics. Driving them independently leaves the legs
+    /// fighting each other, so this is not meant for general use.
+    #[doc(hidden)]
+    pub fn bridges_mut(&mut self) -> (&mut Bridge<IN1, IN2>, &mut Bridge<IN3, IN4>) {
+        (&mut self.split.a, &mut self.split.b)
+    }
+}
+
+/// Provides access to the PWM driving the eep pin, which is either shared with the [MotorDriver](crate::MotorDriver)
+/// behind an `Arc<Mutex<_>>`, owned directly by the driver through [OwnedPwm], or, with the `critical-section-pwm`
+/// feature, shared with interrupt handlers through a static `critical_section::Mutex<RefCell<_>>`.
+pub trait PwmAccess {
+    type Pwm: SetDutyCycle;
+
+    fn with_pwm<R>(&mut self, f: impl FnOnce(&mut Self::Pwm) -> R) -> Result<R, MotorDriverError>;
+
+    fn replace(&mut self, pwm: Self::Pwm) -> Result<Self::Pwm, MotorDriverError> {
+        self.with_pwm(|current| core::mem::replace(current, pwm))
+    }
+}
+
+#[cfg(feature = "std")]
+impl<PWM: SetDutyCycle> PwmAccess for Arc<Mutex<PWM>> {
+    type Pwm = PWM;
+
+    fn with_pwm<R>(&mut self, f: impl FnOnce(&mut Self::Pwm) -> R) -> Result<R, MotorDriverError> {
+        let mut pwm = self.lock().map_err(|_| MotorDriverError::PwmLocked)?;
+
+        Ok(f(&mut pwm))
+    }
 }
 
-impl<IN1, IN2, IN3, IN4, PWM> Driver for PwmParallelDriver<IN1, IN2, IN3, IN4, Arc<Mutex<PWM>>>
+/// A PWM owned exclusively by a [PwmParallelDriver], requiring no locking.
+pub struct OwnedPwm<PWM>(pub PWM);
+
+impl<PWM: SetDutyCycle> PwmAccess for OwnedPwm<PWM> {
+    type Pwm = PWM;
+
+    fn with_pwm<R>(&mut self, f: impl FnOnce(&mut Self::Pwm) -> R) -> Result<R, MotorDriverError> {
+        Ok(f(&mut self.0))
+    }
+}
+
+/// Accesses a PWM shared with interrupt handlers on bare metal, where `std::sync::Mutex` isn't available.
+///
+/// Every access runs inside a critical section, so an interrupt can never observe or change the PWM
+/// halfway through a command. Returns [MotorDriverError::PwmLocked] if the PWM is already borrowed,
+/// e.g. when an interrupt fires while the application holds it outside of this driver.
+#[cfg(any(test, feature = "critical-section-pwm"))]
+impl<PWM: SetDutyCycle> PwmAccess for &'static critical_section::Mutex<RefCell<PWM>> {
+    type Pwm = PWM;
+
+    fn with_pwm<R>(&mut self, f: impl FnOnce(&mut Self::Pwm) -> R) -> Result<R, MotorDriverError> {
+        critical_section::with(|cs| {
+            let mut pwm = self.borrow(cs).try_borrow_mut().map_err(|_| MotorDriverError::PwmLocked)?;
+
+            Ok(f(&mut pwm))
+        })
+    }
+}
+
+impl<IN1, IN2, IN3, IN4, PWM> Driver for PwmParallelDriver<IN1, IN2, IN3, IN4, PWM>
     where
         IN1: OutputPin,
         IN2: OutputPin,
         IN3: OutputPin,
         IN4: OutputPin,
+        PWM: PwmAccess,
 {}
 
-impl<IN1, IN2, IN3, IN4, PWM> PwmParallelDriver<IN1, IN2, IN3, IN4, Arc<Mutex<PWM>>>
+impl<IN1, IN2, IN3, IN4, PWM> PwmParallelDriver<IN1, IN2, IN3, IN4, PWM>
     where
         IN1: OutputPin,
         IN2: OutputPin,
         IN3: OutputPin,
         IN4: OutputPin,
-        PWM: SetDutyCycle,
+        PWM: PwmAccess,
 {
-    pub fn set_min_duty(&mut self, duty: u16) {
+    /// Returns [MotorDriverError::InvalidRange] unless the duty is below the
+    /// [maximum duty](PwmParallelDriver::set_max_duty).
+    pub fn set_min_duty(&mut self, duty: u16) -> Result<(), MotorDriverError> {
+        check_min_duty(duty, duty, self.max_duty)?;
+
         self.min_duty = duty;
+
+        Ok(())
+    }
+
+    /// Caps the duty applied at 100%, see [PwmBridge::set_max_duty](crate::bridge::PwmBridge::set_max_duty).
+    ///
+    /// Returns [MotorDriverError::InvalidRange] unless the ceiling is above `min_duty`.
+    pub fn set_max_duty(&mut self, duty: u16) -> Result<(), MotorDriverError> {
+        if duty <= self.min_duty {
+            return Err(MotorDriverError::InvalidRange);
+        }
+
+        self.max_duty = Some(duty);
+
+        Ok(())
+    }
+
+    /// Sets how percentages are rounded, see [PwmBridge::set_round_mode](crate::bridge::PwmBridge::set_round_mode).
+    pub fn set_round_mode(&mut self, round: RoundMode) {
+        self.round = round;
     }
 
-    fn set_duty_cycle_percent(&self, percent: u8) -> Result<(), MotorDriverError> {
+    /// Sets the [BrakePolicy] applied by [Breaks::stop], see [Bridge::set_brake_policy].
+    pub fn set_brake_policy(&mut self, policy: BrakePolicy) {
+        self.split.a.set_brake_policy(policy);
+        self.split.b.set_brake_policy(policy);
+    }
+
+    /// Swaps in a new PWM handle, e.g. after reconfiguring its timer, returning the previous one.
+    ///
+    /// When the PWM is shared with the [MotorDriver](crate::MotorDriver), the shared handle is replaced
+    /// in place so both keep using the same one. The `min_duty` is preserved, but the new handle's duty
+    /// is left untouched until the next command.
+    pub fn replace_pwm(&mut self, pwm: PWM::Pwm) -> Result<PWM::Pwm, MotorDriverError> {
+        self.pwm.replace(pwm)
+    }
+
+    /// Sets the duty cycle of the PWM applied to the eep pin without changing the direction.
+    ///
+    /// This is also what `set_duty_cycle` resolves to on the [MotorDriver](crate::MotorDriver) of this mode.
+    /// Like the movement commands, it only holds the PWM lock while writing the duty, so the two can be
+    /// freely interleaved without deadlocking and share the duty reported by [Self::duty_cycle_percent].
+    #[must_use = "motor command result must be checked"]
+    pub fn set_duty_cycle(&mut self, percent: u8) -> Result<(), MotorDriverError> {
+        self.set_duty_cycle_percent(percent)
+    }
+
+    /// Returns the last duty percentage successfully applied to the eep pin, either through
+    /// [PwmParallelDriver::set_duty_cycle] or a movement command.
+    pub fn duty_cycle_percent(&self) -> u8 {
+        self.duty
+    }
+
+    /// Runs `f` while holding the PWM lock throughout, so a composite operation, e.g. changing the duty
+    /// and then the direction, is atomic with respect to every other user of a shared PWM. The regular
+    /// commands instead release the lock between the duty and the pin writes.
+    ///
+    /// Returns [MotorDriverError::PwmLocked] without calling `f` if the lock can't be acquired.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use drv8833_driver::{MotorDriver, PwmMovement};
+    /// use embedded_hal_mock::eh1::pin::Mock as Pin;
+    /// use embedded_hal_mock::eh1::pwm::Mock as PwmPin;
+    ///
+    /// let (in1, in2, in3, in4) = (Pin::new(&[]), Pin::new(&[]), Pin::new(&[]), Pin::new(&[]));
+    ///
+    /// let mut motor = MotorDriver::new_pwm_parallel(in1, in2, in3, in4, PwmPin::new(&[]), None::<Pin>);
+    ///
+    /// motor.with_locked(|locked| {
+    ///     locked.set_duty_cycle(30)?;
+    ///     locked.reverse(30)
+    /// })??;
+    /// # Ok::<(), drv8833_driver::MotorDriverError>(())
+    /// ```
+    #[must_use = "motor command result must be checked"]
+    pub fn with_locked<R>(
+        &mut self,
+        f: impl FnOnce(&mut LockedPwmParallel<'_, IN1, IN2, IN3, IN4, PWM::Pwm>) -> R,
+    ) -> Result<R, MotorDriverError> {
+        let Self { pwm, split, min_duty, max_duty, round, duty } = self;
+
+        pwm.with_pwm(|pwm| {
+            f(&mut LockedPwmParallel {
+                pwm,
+                split,
+                min_duty: *min_duty,
+                max_duty: *max_duty,
+                round: *round,
+                duty,
+            })
+        })
+    }
+
+    /// Applies `percent` following the canonical mapping of [remap], see [write_percent].
+    fn set_duty_cycle_percent(&mut self, percent: u8) -> Result<(), MotorDriverError> {
+        check_percent(percent);
+
         if percent > 100 {
             return Err(MotorDriverError::InvalidRange);
         }
 
-        let mut pwm = self.pwm.lock().map_err(|_| MotorDriverError::PwmLocked)?;
+        let (min_duty, max_duty, round) = (self.min_duty, self.max_duty, self.round);
 
-        let result = match percent {
-            0 => pwm.set_duty_cycle_fully_off(),
-            100 => pwm.set_duty_cycle_fully_on(),
-            _ => {
-                let remapped = remap(percent, self.min_duty, pwm.max_duty_cycle());
+        self.pwm.with_pwm(|pwm| write_percent(pwm, percent, min_duty, max_duty, round))??;
 
-                pwm.set_duty_cycle(remapped)
-            }
-        };
+        self.duty = percent;
 
-        result.map_err(|_| MotorDriverError::UnableToSetDuty)?;
+        Ok(())
+    }
+}
+
+/// Writes `percent` to an already accessed PWM following the canonical mapping of [remap], using the fully
+/// off/on shortcuts for the endpoints which map to the same duty as `remap` would, unless a ceiling is configured.
+fn write_percent<P: SetDutyCycle>(
+    pwm: &mut P,
+    percent: u8,
+    min_duty: u16,
+    max_duty: Option<u16>,
+    round: RoundMode,
+) -> Result<(), MotorDriverError> {
+    let result = match percent {
+        0 => pwm.set_duty_cycle_fully_off(),
+        100 if max_duty.is_none() => pwm.set_duty_cycle_fully_on(),
+        _ => {
+            let max = pwm.max_duty_cycle();
+            let remapped = remap(percent, min_duty, max_duty.map_or(max, |duty| duty.min(max)), round);
+
+            pwm.set_duty_cycle(remapped)
+        }
+    };
+
+    result.map_err(|_| MotorDriverError::UnableToSetDuty)
+}
+
+/// A [PwmParallelDriver] whose PWM is held locked for the duration of [PwmParallelDriver::with_locked].
+///
+/// It accepts the same commands as the driver itself, none of which acquire the lock again.
+pub struct LockedPwmParallel<'a, IN1, IN2, IN3, IN4, PWM>
+    where
+        IN1: OutputPin,
+        IN2: OutputPin,
+        IN3: OutputPin,
+        IN4: OutputPin,
+{
+    pwm: &'a mut PWM,
+    split: &'a mut SplitDriver<IN1, IN2, IN3, IN4>,
+    min_duty: u16,
+    max_duty: Option<u16>,
+    round: RoundMode,
+    duty: &'a mut u8,
+}
+
+impl<IN1, IN2, IN3, IN4, PWM> LockedPwmParallel<'_, IN1, IN2, IN3, IN4, PWM>
+    where
+        IN1: OutputPin,
+        IN2: OutputPin,
+        IN3: OutputPin,
+        IN4: OutputPin,
+        PWM: SetDutyCycle,
+{
+    /// See [PwmParallelDriver::set_duty_cycle].
+    #[must_use = "motor command result must be checked"]
+    pub fn set_duty_cycle(&mut self, percent: u8) -> Result<(), MotorDriverError> {
+        check_percent(percent);
+
+        if percent > 100 {
+            return Err(MotorDriverError::InvalidRange);
+        }
+
+        write_percent(self.pwm, percent, self.min_duty, self.max_duty, self.round)?;
+
+        *self.duty = percent;
 
         Ok(())
     }
+
+    /// See [PwmParallelDriver::duty_cycle_percent].
+    pub fn duty_cycle_percent(&self) -> u8 {
+        *self.duty
+    }
 }
 
-impl<IN1, IN2, IN3, IN4, PWM> PwmMovement for PwmParallelDriver<IN1, IN2, IN3, IN4, Arc<Mutex<PWM>>>
+impl<IN1, IN2, IN3, IN4, PWM> PwmMovement for LockedPwmParallel<'_, IN1, IN2, IN3, IN4, PWM>
     where
         IN1: OutputPin,
         IN2: OutputPin,
         IN3: OutputPin,
         IN4: OutputPin,
         PWM: SetDutyCycle,
+{
+    fn forward(&mut self, percent: u8) -> Result<(), MotorDriverError> {
+        self.set_duty_cycle(percent)?;
+
+        self.split.a.forward()?;
+        self.split.b.forward()?;
+
+        Ok(())
+    }
+
+    fn reverse(&mut self, percent: u8) -> Result<(), MotorDriverError> {
+        self.set_duty_cycle(percent)?;
+
+        self.split.a.reverse()?;
+        self.split.b.reverse()?;
+
+        Ok(())
+    }
+}
+
+impl<IN1, IN2, IN3, IN4, PWM> Breaks for LockedPwmParallel<'_, IN1, IN2, IN3, IN4, PWM>
+    where
+        IN1: OutputPin,
+        IN2: OutputPin,
+        IN3: OutputPin,
+        IN4: OutputPin,
+        PWM: SetDutyCycle,
+{
+    fn coast(&mut self) -> Result<(), MotorDriverError> {
+        self.set_duty_cycle(0)?;
+
+        self.split.a.coast()?;
+        self.split.b.coast()?;
+
+        Ok(())
+    }
+
+    fn stop(&mut self) -> Result<(), MotorDriverError> {
+        if !self.split.a.brake_allowed()? {
+            return self.coast();
+        }
+
+        self.pwm.set_duty_cycle_fully_on().map_err(|_| MotorDriverError::UnableToSetDuty)?;
+
+        *self.duty = 100;
+
+        self.split.a.stop()?;
+        self.split.b.stop()?;
+
+        Ok(())
+    }
+}
+
+impl<IN1, IN2, IN3, IN4, PWM> crate::driver::DynMotor for LockedPwmParallel<'_, IN1, IN2, IN3, IN4, PWM>
+    where
+        IN1: OutputPin,
+        IN2: OutputPin,
+        IN3: OutputPin,
+        IN4: OutputPin,
+        PWM: SetDutyCycle,
+{
+    fn forward(&mut self, percent: u8) -> Result<(), MotorDriverError> {
+        PwmMovement::forward(self, percent)
+    }
+
+    fn reverse(&mut self, percent: u8) -> Result<(), MotorDriverError> {
+        PwmMovement::reverse(self, percent)
+    }
+
+    fn coast(&mut self) -> Result<(), MotorDriverError> {
+        Breaks::coast(self)
+    }
+
+    fn stop(&mut self) -> Result<(), MotorDriverError> {
+        Breaks::stop(self)
+    }
+}
+
+impl<IN1, IN2, IN3, IN4, PWM> PwmMovement for PwmParallelDriver<IN1, IN2, IN3, IN4, PWM>
+    where
+        IN1: OutputPin,
+        IN2: OutputPin,
+        IN3: OutputPin,
+        IN4: OutputPin,
+        PWM: PwmAccess,
 {
     fn forward(&mut self, percent: u8) -> Result<(), MotorDriverError> {
         self.set_duty_cycle_percent(percent)?;
@@ -105,13 +423,55 @@ impl<IN1, IN2, IN3, IN4, PWM> PwmMovement for PwmParallelDriver<IN1, IN2, IN3, I
     }
 }
 
-impl<IN1, IN2, IN3, IN4, PWM> Breaks for PwmParallelDriver<IN1, IN2, IN3, IN4, Arc<Mutex<PWM>>>
+impl<IN1, IN2, IN3, IN4, PWM> crate::driver::DynMotor for PwmParallelDriver<IN1, IN2, IN3, IN4, PWM>
     where
         IN1: OutputPin,
         IN2: OutputPin,
         IN3: OutputPin,
         IN4: OutputPin,
-        PWM: SetDutyCycle,
+        PWM: PwmAccess,
+{
+    fn forward(&mut self, percent: u8) -> Result<(), MotorDriverError> {
+        PwmMovement::forward(self, percent)
+    }
+
+    fn reverse(&mut self, percent: u8) -> Result<(), MotorDriverError> {
+        PwmMovement::reverse(self, percent)
+    }
+
+    fn coast(&mut self) -> Result<(), MotorDriverError> {
+        Breaks::coast(self)
+    }
+
+    fn stop(&mut self) -> Result<(), MotorDriverError> {
+        Breaks::stop(self)
+    }
+}
+
+/// The duty applied to the eep pin scales the load, as the IN pins only set the direction.
+impl<IN1, IN2, IN3, IN4, PWM> CurrentLoad for PwmParallelDriver<IN1, IN2, IN3, IN4, PWM>
+    where
+        IN1: OutputPin,
+        IN2: OutputPin,
+        IN3: OutputPin,
+        IN4: OutputPin,
+        PWM: PwmAccess,
+{
+    fn load_percent(&self) -> u16 {
+        match self.split.a.state().percent() {
+            0 => 0,
+            _ => 2 * self.duty as u16,
+        }
+    }
+}
+
+impl<IN1, IN2, IN3, IN4, PWM> Breaks for PwmParallelDriver<IN1, IN2, IN3, IN4, PWM>
+    where
+        IN1: OutputPin,
+        IN2: OutputPin,
+        IN3: OutputPin,
+        IN4: OutputPin,
+        PWM: PwmAccess,
 {
     fn coast(&mut self) -> Result<(), MotorDriverError> {
         self.set_duty_cycle_percent(0)?;
@@ -123,7 +483,15 @@ impl<IN1, IN2, IN3, IN4, PWM> Breaks for PwmParallelDriver<IN1, IN2, IN3, IN4, A
     }
 
     fn stop(&mut self) -> Result<(), MotorDriverError> {
-        self.set_duty_cycle_percent(100)?;
+        if !self.split.a.brake_allowed()? {
+            return self.coast();
+        }
+
+        self.pwm
+            .with_pwm(|pwm| pwm.set_duty_cycle_fully_on())?
+            .map_err(|_| MotorDriverError::UnableToSetDuty)?;
+
+        self.duty = 100;
 
         self.split.a.stop()?;
         self.split.b.stop()?;
@@ -134,6 +502,12 @@ impl<IN1, IN2, IN3, IN4, PWM> Breaks for PwmParallelDriver<IN1, IN2, IN3, IN4, A
 
 #[cfg(test)]
 mod tests {
+    use std::cell::RefCell;
+    #[cfg(feature = "std")]
+    use std::sync::{Arc, Mutex as StdMutex};
+
+    use critical_section::Mutex;
+    use embedded_hal::pwm::SetDutyCycle;
     use embedded_hal_mock::eh1::pin::Mock as Pin;
     use embedded_hal_mock::eh1::pin::State::{High, Low};
     use embedded_hal_mock::eh1::pin::Transaction;
@@ -141,8 +515,11 @@ mod tests {
     use embedded_hal_mock::eh1::pwm::Transaction as PwmPinTransaction;
 
     use crate::driver::{Breaks, MotorDriver, MotorDriverError, PwmMovement};
+    #[cfg(feature = "std")]
+    use crate::pwm_parallel_driver::PwmParallelDriver;
 
     #[test]
+    #[cfg(feature = "std")]
     fn test_it_can_drive_each_bridge_independently() -> Result<(), MotorDriverError> {
         let mut in1 = Pin::new(&[
             Transaction::set(High),
@@ -200,4 +577,277 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_replaced_pwm_is_used_by_subsequent_commands() -> Result<(), MotorDriverError> {
+        let mut in1 = Pin::new(&[Transaction::set(High)]);
+        let mut in2 = Pin::new(&[Transaction::set(Low)]);
+        let mut in3 = Pin::new(&[Transaction::set(High)]);
+        let mut in4 = Pin::new(&[Transaction::set(Low)]);
+
+        let mut old = PwmPin::new(&[]);
+        let mut new = PwmPin::new(&[PwmPinTransaction::max_duty_cycle(1000), PwmPinTransaction::set_duty_cycle(500)]);
+
+        let mut motor = MotorDriver::new_pwm_parallel(
+            in1.clone(), in2.clone(), in3.clone(), in4.clone(), old.clone(), None::<Pin>,
+        );
+
+        motor.replace_pwm(new.clone())?;
+        motor.forward(50)?;
+
+        in1.done();
+        in2.done();
+        in3.done();
+        in4.done();
+
+        old.done();
+        new.done();
+
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_full_speed_is_capped_at_the_max_duty() -> Result<(), MotorDriverError> {
+        let mut in1 = Pin::new(&[Transaction::set(High), Transaction::set(High)]);
+        let mut in2 = Pin::new(&[Transaction::set(Low), Transaction::set(High)]);
+        let mut in3 = Pin::new(&[Transaction::set(High), Transaction::set(High)]);
+        let mut in4 = Pin::new(&[Transaction::set(Low), Transaction::set(High)]);
+
+        let mut pwm = PwmPin::new(&[
+            PwmPinTransaction::max_duty_cycle(1000),
+            PwmPinTransaction::set_duty_cycle(750),
+            PwmPinTransaction::max_duty_cycle(1000),
+            PwmPinTransaction::set_duty_cycle(1000),
+        ]);
+
+        let mut motor = MotorDriver::new_pwm_parallel(
+            in1.clone(), in2.clone(), in3.clone(), in4.clone(), pwm.clone(), None::<Pin>,
+        );
+
+        motor.set_min_duty(100)?;
+
+        assert!(matches!(motor.set_max_duty(50), Err(MotorDriverError::InvalidRange)));
+
+        motor.set_max_duty(750)?;
+
+        assert!(matches!(motor.set_min_duty(750), Err(MotorDriverError::InvalidRange)));
+
+        motor.forward(100)?;
+        motor.stop()?;
+
+        in1.done();
+        in2.done();
+        in3.done();
+        in4.done();
+
+        pwm.done();
+
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_composite_operations_hold_the_lock_throughout() -> Result<(), MotorDriverError> {
+        let mut in1 = Pin::new(&[Transaction::set(Low)]);
+        let mut in2 = Pin::new(&[Transaction::set(High)]);
+        let mut in3 = Pin::new(&[Transaction::set(Low)]);
+        let mut in4 = Pin::new(&[Transaction::set(High)]);
+
+        let mut pwm = PwmPin::new(&[
+            PwmPinTransaction::max_duty_cycle(100),
+            PwmPinTransaction::set_duty_cycle(30),
+            PwmPinTransaction::max_duty_cycle(100),
+            PwmPinTransaction::set_duty_cycle(30),
+        ]);
+
+        let shared = Arc::new(StdMutex::new(pwm.clone()));
+
+        let mut driver = PwmParallelDriver::new(in1.clone(), in2.clone(), in3.clone(), in4.clone(), shared.clone());
+
+        driver.with_locked(|locked| {
+            locked.set_duty_cycle(30)?;
+
+            assert!(shared.try_lock().is_err());
+
+            PwmMovement::reverse(locked, 30)
+        })??;
+
+        assert!(shared.try_lock().is_ok());
+        assert_eq!(driver.duty_cycle_percent(), 30);
+
+        in1.done();
+        in2.done();
+        in3.done();
+        in4.done();
+
+        pwm.done();
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_single_owner_drives_without_shared_pwm() -> Result<(), MotorDriverError> {
+        let mut in1 = Pin::new(&[Transaction::set(High), Transaction::set(Low)]);
+        let mut in2 = Pin::new(&[Transaction::set(Low), Transaction::set(Low)]);
+        let mut in3 = Pin::new(&[Transaction::set(High), Transaction::set(Low)]);
+        let mut in4 = Pin::new(&[Transaction::set(Low), Transaction::set(Low)]);
+
+        let mut pwm = PwmPin::new(&[
+            PwmPinTransaction::max_duty_cycle(100),
+            PwmPinTransaction::set_duty_cycle(50),
+            PwmPinTransaction::max_duty_cycle(100),
+            PwmPinTransaction::set_duty_cycle(80),
+            PwmPinTransaction::set_duty_cycle(0),
+        ]);
+
+        let mut motor = MotorDriver::new_pwm_parallel_single_owner(
+            in1.clone(), in2.clone(), in3.clone(), in4.clone(), pwm.clone(), None::<Pin>,
+        );
+
+        motor.forward(50)?;
+        motor.set_duty_cycle(80)?;
+
+        assert_eq!(motor.duty_cycle_percent(), 80);
+
+        motor.coast()?;
+
+        assert_eq!(motor.duty_cycle_percent(), 0);
+
+        in1.done();
+        in2.done();
+        in3.done();
+        in4.done();
+
+        pwm.done();
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_critical_section_pwm_is_shared_with_the_application() -> Result<(), MotorDriverError> {
+        let mut in1 = Pin::new(&[Transaction::set(High)]);
+        let mut in2 = Pin::new(&[Transaction::set(Low)]);
+        let mut in3 = Pin::new(&[Transaction::set(High)]);
+        let mut in4 = Pin::new(&[Transaction::set(Low)]);
+
+        let mut pwm = PwmPin::new(&[
+            PwmPinTransaction::max_duty_cycle(100),
+            PwmPinTransaction::set_duty_cycle(50),
+            PwmPinTransaction::set_duty_cycle(0),
+        ]);
+
+        let shared: &'static Mutex<RefCell<PwmPin>> = Box::leak(Box::new(Mutex::new(RefCell::new(pwm.clone()))));
+
+        let mut motor = MotorDriver::new_pwm_parallel_critical_section(
+            in1.clone(), in2.clone(), in3.clone(), in4.clone(), shared, None::<Pin>,
+        );
+
+        motor.forward(50)?;
+
+        critical_section::with(|cs| {
+            let _borrowed = shared.borrow_ref_mut(cs);
+
+            assert!(matches!(motor.set_duty_cycle(80), Err(MotorDriverError::PwmLocked)));
+        });
+
+        critical_section::with(|cs| shared.borrow_ref_mut(cs).set_duty_cycle_fully_off())
+            .map_err(|_| MotorDriverError::UnableToSetDuty)?;
+
+        in1.done();
+        in2.done();
+        in3.done();
+        in4.done();
+
+        pwm.done();
+
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_duty_readback_follows_both_duty_and_movement_commands() -> Result<(), MotorDriverError> {
+        let mut in1 = Pin::new(&[Transaction::set(High), Transaction::set(Low), Transaction::set(Low)]);
+        let mut in2 = Pin::new(&[Transaction::set(Low), Transaction::set(High), Transaction::set(Low)]);
+        let mut in3 = Pin::new(&[Transaction::set(High), Transaction::set(Low), Transaction::set(Low)]);
+        let mut in4 = Pin::new(&[Transaction::set(Low), Transaction::set(High), Transaction::set(Low)]);
+
+        let mut pwm = PwmPin::new(&[
+            PwmPinTransaction::max_duty_cycle(100),
+            PwmPinTransaction::set_duty_cycle(50),
+            PwmPinTransaction::max_duty_cycle(100),
+            PwmPinTransaction::set_duty_cycle(80),
+            PwmPinTransaction::max_duty_cycle(100),
+            PwmPinTransaction::set_duty_cycle(30),
+            PwmPinTransaction::set_duty_cycle(0),
+        ]);
+
+        let mut motor = MotorDriver::new_pwm_parallel(
+            in1.clone(), in2.clone(), in3.clone(), in4.clone(), pwm.clone(), None::<Pin>,
+        );
+
+        motor.forward(50)?;
+
+        assert_eq!(motor.duty_cycle_percent(), 50);
+
+        motor.set_duty_cycle(80)?;
+
+        assert_eq!(motor.duty_cycle_percent(), 80);
+        assert!(motor.pwm.try_lock().is_ok());
+
+        motor.reverse(30)?;
+
+        assert_eq!(motor.duty_cycle_percent(), 30);
+
+        motor.coast()?;
+
+        assert_eq!(motor.duty_cycle_percent(), 0);
+
+        in1.done();
+        in2.done();
+        in3.done();
+        in4.done();
+
+        pwm.done();
+
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_endpoints_follow_the_canonical_mapping() -> Result<(), MotorDriverError> {
+        let mut in1 = Pin::new(&[Transaction::set(High)]);
+        let mut in2 = Pin::new(&[Transaction::set(Low)]);
+        let mut in3 = Pin::new(&[Transaction::set(High)]);
+        let mut in4 = Pin::new(&[Transaction::set(Low)]);
+
+        let mut pwm = PwmPin::new(&[
+            PwmPinTransaction::set_duty_cycle(0),
+            PwmPinTransaction::max_duty_cycle(1000),
+            PwmPinTransaction::set_duty_cycle(425),
+            PwmPinTransaction::max_duty_cycle(1000),
+            PwmPinTransaction::set_duty_cycle(750),
+        ]);
+
+        let mut motor = MotorDriver::new_pwm_parallel(
+            in1.clone(), in2.clone(), in3.clone(), in4.clone(), pwm.clone(), None::<Pin>,
+        );
+
+        motor.set_min_duty(100)?;
+        motor.set_max_duty(750)?;
+
+        motor.forward(0)?;
+        motor.forward(50)?;
+        motor.forward(100)?;
+
+        in1.done();
+        in2.done();
+        in3.done();
+        in4.done();
+
+        pwm.done();
+
+        Ok(())
+    }
 }
@@ -1,5 +1,8 @@
-use crate::bridge::PwmBridge;
-use crate::driver::Driver;
+use embedded_hal::pwm::SetDutyCycle;
+
+use crate::bridge::{check_min_duty, remap, PwmBridge};
+use crate::config::DriverConfig;
+use crate::driver::{Breaks, BridgeStates, CurrentLoad, Decay, Direction, Driver, MotorDriverError, MotorState};
 
 pub struct PwmSplitDriver<IN1, IN2, IN3, IN4> {
     pub a: PwmBridge<IN1, IN2>,
@@ -13,26 +16,165 @@ impl<IN1, IN2, IN3, IN4> PwmSplitDriver<IN1, IN2, IN3, IN4> {
             b: PwmBridge::new(in3, in4, 0),
         }
     }
+
+    /// Same as [PwmSplitDriver::new], but in debug builds asserts that the four pins are distinct PWM
+    /// channels, catching a cloneable channel handle passed to more than one IN slot.
+    pub fn new_checked(in1: IN1, in2: IN2, in3: IN3, in4: IN4) -> Self
+        where
+            IN1: PwmChannelId,
+            IN2: PwmChannelId,
+            IN3: PwmChannelId,
+            IN4: PwmChannelId,
+    {
+        let ids = [in1.channel_id(), in2.channel_id(), in3.channel_id(), in4.channel_id()];
+
+        for (index, id) in ids.iter().enumerate() {
+            debug_assert!(!ids[index + 1..].contains(id), "PWM channel {} is used by more than one IN pin", id);
+        }
+
+        Self::new(in1, in2, in3, in4)
+    }
+}
+
+/// Optionally implemented by PWM channels which can tell which hardware channel they drive, so
+/// [MotorDriver::new_pwm_split_checked](crate::MotorDriver::new_pwm_split_checked) can detect the
+/// same channel being used for two IN pins.
+pub trait PwmChannelId {
+    /// Returns an identifier unique to the hardware channel, e.g. the timer and channel numbers combined.
+    fn channel_id(&self) -> u32;
 }
 
 impl<IN1, IN2, IN3, IN4> Driver for PwmSplitDriver<IN1, IN2, IN3, IN4> {}
 
+impl<IN1, IN2, IN3, IN4> Breaks for PwmSplitDriver<IN1, IN2, IN3, IN4>
+    where
+        IN1: SetDutyCycle,
+        IN2: SetDutyCycle,
+        IN3: SetDutyCycle,
+        IN4: SetDutyCycle,
+{
+    fn coast(&mut self) -> Result<(), MotorDriverError> {
+        self.a.coast()?;
+        self.b.coast()?;
+
+        Ok(())
+    }
+
+    fn stop(&mut self) -> Result<(), MotorDriverError> {
+        self.a.stop()?;
+        self.b.stop()?;
+
+        Ok(())
+    }
+}
+
+impl<IN1, IN2, IN3, IN4> PwmSplitDriver<IN1, IN2, IN3, IN4>
+    where
+        IN1: SetDutyCycle,
+        IN2: SetDutyCycle,
+        IN3: SetDutyCycle,
+        IN4: SetDutyCycle,
+{
+    /// Drives both bridges in the same direction with an identical duty, for a single motor driven by
+    /// both bridges in software rather than wired in parallel.
+    ///
+    /// The duty is remapped once using bridge A's `min_duty` for the direction, `max_duty`, minimum on-time and
+    /// PWM resolution, and then written as is to both bridges, so both legs always receive the exact same value.
+    /// A zero percentage coasts both bridges, following the [mapping of every PWM path](crate::PwmBridge).
+    #[must_use = "motor command result must be checked"]
+    pub fn drive_combined(&mut self, direction: Direction, percent: u8) -> Result<(), MotorDriverError> {
+        if percent == 0 {
+            return self.coast();
+        }
+
+        let max = self.a.max_duty_cycle(direction);
+        let duty = self.a.snap_on_time(remap(percent, self.a.min_duty_for(direction), self.a.ceiling(max), self.a.round_mode()), max);
+
+        self.a.apply_duty(direction, percent, duty, max)?;
+        self.b.apply_duty(direction, percent, duty, max)?;
+
+        Ok(())
+    }
+}
+
 impl<IN1, IN2, IN3, IN4> PwmSplitDriver<IN1, IN2, IN3, IN4> {
-    pub fn set_min_duty(&mut self, duty: u16) {
-        self.a.set_min_duty(duty);
-        self.b.set_min_duty(duty);
+    /// Sets the same minimum duty on both bridges, see [PwmSplitDriver::set_min_duty_a] and
+    /// [PwmSplitDriver::set_min_duty_b] to tune motors with different stall thresholds independently.
+    ///
+    /// Returns [MotorDriverError::InvalidRange], leaving both bridges unchanged, unless the duty is below the
+    /// maximum duty of each bridge.
+    pub fn set_min_duty(&mut self, duty: u16) -> Result<(), MotorDriverError> {
+        check_min_duty(duty, duty, self.a.max_duty())?;
+        check_min_duty(duty, duty, self.b.max_duty())?;
+
+        self.a.set_min_duty(duty)?;
+        self.b.set_min_duty(duty)
+    }
+
+    /// Sets the minimum duty of bridge A only, see [PwmBridge::set_min_duty](crate::PwmBridge::set_min_duty).
+    pub fn set_min_duty_a(&mut self, duty: u16) -> Result<(), MotorDriverError> {
+        self.a.set_min_duty(duty)
+    }
+
+    /// Sets the minimum duty of bridge B only, see [PwmBridge::set_min_duty](crate::PwmBridge::set_min_duty).
+    pub fn set_min_duty_b(&mut self, duty: u16) -> Result<(), MotorDriverError> {
+        self.b.set_min_duty(duty)
+    }
+
+    pub fn set_decay(&mut self, decay: Decay) {
+        self.a.set_decay(decay);
+        self.b.set_decay(decay);
+    }
+
+    /// Applies the same settings to both bridges, see [PwmBridge::apply_config](crate::PwmBridge::apply_config).
+    pub fn apply_config(&mut self, config: &DriverConfig) -> Result<(), MotorDriverError> {
+        self.a.apply_config(config)?;
+        self.b.apply_config(config)
+    }
+}
+
+impl<IN1, IN2, IN3, IN4> CurrentLoad for PwmSplitDriver<IN1, IN2, IN3, IN4>
+    where
+        IN1: SetDutyCycle,
+        IN2: SetDutyCycle,
+        IN3: SetDutyCycle,
+        IN4: SetDutyCycle,
+{
+    fn load_percent(&self) -> u16 {
+        self.a.state().percent() as u16 + self.b.state().percent() as u16
+    }
+}
+
+impl<IN1, IN2, IN3, IN4> BridgeStates for PwmSplitDriver<IN1, IN2, IN3, IN4>
+    where
+        IN1: SetDutyCycle,
+        IN2: SetDutyCycle,
+        IN3: SetDutyCycle,
+        IN4: SetDutyCycle,
+{
+    fn bridge_states(&self) -> [MotorState; 2] {
+        [self.a.state(), self.b.state()]
+    }
+
+    fn restore_bridge_states(&mut self, states: [MotorState; 2]) -> Result<(), MotorDriverError> {
+        self.a.set_state(states[0])?;
+        self.b.set_state(states[1])
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use std::convert::Infallible;
+
+    use embedded_hal::pwm::{ErrorType, SetDutyCycle};
     use embedded_hal_mock::eh1::pin::Mock as Pin;
     use embedded_hal_mock::eh1::pin::State::{High, Low};
     use embedded_hal_mock::eh1::pin::Transaction;
     use embedded_hal_mock::eh1::pwm::Mock as PwmPin;
     use embedded_hal_mock::eh1::pwm::Transaction as PwmPinTransaction;
 
-    use crate::driver::{Breaks, MotorDriver, MotorDriverError, PwmMovement};
+    use crate::driver::{Breaks, Decay, Direction, MotorDriver, MotorDriverError, PwmMovement};
+    use crate::pwm_split_driver::PwmChannelId;
 
     #[test]
     fn test_it_can_drive_each_bridge_independently() -> Result<(), MotorDriverError> {
@@ -63,6 +205,7 @@ mod tests {
             in1.clone(), in2.clone(), in3.clone(), in4.clone(), Some(sleep.clone()), None::<Pin>,
         );
 
+        motor.set_decay(Decay::Fast);
         motor.wakeup()?;
 
         motor.a.forward(50)?;
@@ -82,4 +225,130 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_each_bridge_can_hold_its_own_min_duty() -> Result<(), MotorDriverError> {
+        let mut in1 = PwmPin::new(&[PwmPinTransaction::max_duty_cycle(100), PwmPinTransaction::set_duty_cycle(60)]);
+        let mut in2 = PwmPin::new(&[PwmPinTransaction::set_duty_cycle(0)]);
+        let mut in3 = PwmPin::new(&[PwmPinTransaction::max_duty_cycle(100), PwmPinTransaction::set_duty_cycle(75)]);
+        let mut in4 = PwmPin::new(&[PwmPinTransaction::set_duty_cycle(0)]);
+
+        let mut motor = MotorDriver::new_pwm_split(
+            in1.clone(), in2.clone(), in3.clone(), in4.clone(), None::<Pin>, None::<Pin>,
+        );
+
+        motor.set_decay(Decay::Fast);
+        motor.set_min_duty_a(20)?;
+        motor.set_min_duty_b(50)?;
+
+        motor.a.forward(50)?;
+        motor.b.forward(50)?;
+
+        in1.done();
+        in2.done();
+        in3.done();
+        in4.done();
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_a_min_duty_rejected_by_either_bridge_changes_neither() -> Result<(), MotorDriverError> {
+        let (mut in1, mut in2, mut in3, mut in4) = (PwmPin::new(&[]), PwmPin::new(&[]), PwmPin::new(&[]), PwmPin::new(&[]));
+
+        let mut motor = MotorDriver::new_pwm_split(
+            in1.clone(), in2.clone(), in3.clone(), in4.clone(), None::<Pin>, None::<Pin>,
+        );
+
+        motor.b.set_max_duty(400)?;
+
+        assert!(matches!(motor.set_min_duty(600), Err(MotorDriverError::InvalidRange)));
+        assert_eq!((motor.a.min_duty(), motor.b.min_duty()), (0, 0));
+
+        in1.done();
+        in2.done();
+        in3.done();
+        in4.done();
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_combined_drive_writes_the_same_duty_to_both_bridges() -> Result<(), MotorDriverError> {
+        let mut in1 = PwmPin::new(&[PwmPinTransaction::max_duty_cycle(1000), PwmPinTransaction::set_duty_cycle(400)]);
+        let mut in2 = PwmPin::new(&[PwmPinTransaction::set_duty_cycle(0)]);
+        let mut in3 = PwmPin::new(&[PwmPinTransaction::set_duty_cycle(400)]);
+        let mut in4 = PwmPin::new(&[PwmPinTransaction::set_duty_cycle(0)]);
+
+        let mut motor = MotorDriver::new_pwm_split(
+            in1.clone(), in2.clone(), in3.clone(), in4.clone(), None::<Pin>, None::<Pin>,
+        );
+
+        motor.set_decay(Decay::Fast);
+        motor.drive_combined(Direction::Forward, 40)?;
+
+        in1.done();
+        in2.done();
+        in3.done();
+        in4.done();
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_combined_drive_at_zero_coasts_in_slow_decay() -> Result<(), MotorDriverError> {
+        let mut in1 = PwmPin::new(&[PwmPinTransaction::set_duty_cycle(0)]);
+        let mut in2 = PwmPin::new(&[PwmPinTransaction::set_duty_cycle(0)]);
+        let mut in3 = PwmPin::new(&[PwmPinTransaction::set_duty_cycle(0)]);
+        let mut in4 = PwmPin::new(&[PwmPinTransaction::set_duty_cycle(0)]);
+
+        let mut motor = MotorDriver::new_pwm_split(
+            in1.clone(), in2.clone(), in3.clone(), in4.clone(), None::<Pin>, None::<Pin>,
+        );
+
+        motor.set_decay(Decay::Slow);
+        motor.set_min_duty(200)?;
+        motor.drive_combined(Direction::Forward, 0)?;
+
+        in1.done();
+        in2.done();
+        in3.done();
+        in4.done();
+
+        Ok(())
+    }
+
+    struct Channel(u32);
+
+    impl ErrorType for Channel {
+        type Error = Infallible;
+    }
+
+    impl SetDutyCycle for Channel {
+        fn max_duty_cycle(&self) -> u16 {
+            100
+        }
+
+        fn set_duty_cycle(&mut self, _: u16) -> Result<(), Self::Error> {
+            Ok(())
+        }
+    }
+
+    impl PwmChannelId for Channel {
+        fn channel_id(&self) -> u32 {
+            self.0
+        }
+    }
+
+    #[test]
+    fn test_distinct_channels_are_accepted() {
+        MotorDriver::new_pwm_split_checked(Channel(0), Channel(1), Channel(2), Channel(3), None::<Pin>, None::<Pin>);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "PWM channel 1 is used by more than one IN pin")]
+    fn test_same_channel_on_two_pins_is_rejected() {
+        MotorDriver::new_pwm_split_checked(Channel(0), Channel(1), Channel(2), Channel(1), None::<Pin>, None::<Pin>);
+    }
 }
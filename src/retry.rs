@@ -0,0 +1,156 @@
+use embedded_hal::delay::DelayNs;
+use embedded_hal::digital::{self, OutputPin};
+use embedded_hal::pwm::{self, SetDutyCycle};
+
+/// Wraps a pin whose writes may transiently fail, such as the pins of an I2C/SPI I/O expander, and
+/// retries each failed write up to a given number of times before giving up.
+///
+/// Pins that are not wrapped are never retried, so this is entirely opt-in per pin.
+///
+/// # Example
+///
+/// ```ignore
+/// let in1 = RetryPin::new(expander_pins.p0, delay.clone(), 2, 100);
+/// let in2 = RetryPin::new(expander_pins.p1, delay.clone(), 2, 100);
+///
+/// let mut motor = MotorDriver::new_split(in1, in2, in3, in4, None::<Pin>, None::<Pin>);
+/// ```
+pub struct RetryPin<PIN, DELAY> {
+    pin: PIN,
+    delay: DELAY,
+    retries: u8,
+    backoff_us: u32,
+}
+
+impl<PIN, DELAY: DelayNs> RetryPin<PIN, DELAY> {
+    /// Creates a new wrapper retrying up to `retries` times, waiting `backoff_us` microseconds
+    /// between attempts.
+    pub fn new(pin: PIN, delay: DELAY, retries: u8, backoff_us: u32) -> Self {
+        Self {
+            pin,
+            delay,
+            retries,
+            backoff_us,
+        }
+    }
+
+    /// Releases the wrapped pin and delay provider.
+    pub fn release(self) -> (PIN, DELAY) {
+        (self.pin, self.delay)
+    }
+
+    fn retry<E>(&mut self, mut write: impl FnMut(&mut PIN) -> Result<(), E>) -> Result<(), E> {
+        let mut attempt = 0;
+
+        loop {
+            match write(&mut self.pin) {
+                Err(_) if attempt < self.retries => {
+                    attempt += 1;
+                    self.delay.delay_us(self.backoff_us);
+                }
+                result => return result,
+            }
+        }
+    }
+}
+
+impl<PIN: digital::ErrorType, DELAY> digital::ErrorType for RetryPin<PIN, DELAY> {
+    type Error = PIN::Error;
+}
+
+impl<PIN: OutputPin, DELAY: DelayNs> OutputPin for RetryPin<PIN, DELAY> {
+    fn set_low(&mut self) -> Result<(), Self::Error> {
+        self.retry(|pin| pin.set_low())
+    }
+
+    fn set_high(&mut self) -> Result<(), Self::Error> {
+        self.retry(|pin| pin.set_high())
+    }
+}
+
+impl<PIN: pwm::ErrorType, DELAY> pwm::ErrorType for RetryPin<PIN, DELAY> {
+    type Error = PIN::Error;
+}
+
+impl<PIN: SetDutyCycle, DELAY: DelayNs> SetDutyCycle for RetryPin<PIN, DELAY> {
+    fn max_duty_cycle(&self) -> u16 {
+        self.pin.max_duty_cycle()
+    }
+
+    fn set_duty_cycle(&mut self, duty: u16) -> Result<(), Self::Error> {
+        self.retry(|pin| pin.set_duty_cycle(duty))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::ErrorKind;
+
+    use embedded_hal_mock::eh1::delay::NoopDelay;
+    use embedded_hal_mock::eh1::pin::Mock as Pin;
+    use embedded_hal_mock::eh1::pin::State::{High, Low};
+    use embedded_hal_mock::eh1::pin::Transaction;
+    use embedded_hal_mock::eh1::MockError;
+
+    use crate::driver::{MotorDriver, MotorDriverError, Movement};
+    use crate::retry::RetryPin;
+
+    #[test]
+    fn test_a_transient_failure_is_retried() -> Result<(), MotorDriverError> {
+        let mut in1 = Pin::new(&[
+            Transaction::set(High).with_error(MockError::Io(ErrorKind::Other)),
+            Transaction::set(High),
+        ]);
+
+        let mut in2 = Pin::new(&[Transaction::set(Low)]);
+        let mut in3 = Pin::new(&[]);
+        let mut in4 = Pin::new(&[]);
+
+        let mut motor = MotorDriver::new_split(
+            RetryPin::new(in1.clone(), NoopDelay::new(), 1, 100),
+            RetryPin::new(in2.clone(), NoopDelay::new(), 1, 100),
+            in3.clone(),
+            in4.clone(),
+            None::<Pin>,
+            None::<Pin>,
+        );
+
+        motor.a.forward()?;
+
+        in1.done();
+        in2.done();
+        in3.done();
+        in4.done();
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_gives_up_after_the_configured_retries() {
+        let mut in1 = Pin::new(&[
+            Transaction::set(High).with_error(MockError::Io(ErrorKind::Other)),
+            Transaction::set(High).with_error(MockError::Io(ErrorKind::Other)),
+            Transaction::set(Low),
+        ]);
+
+        let mut in2 = Pin::new(&[Transaction::set(Low)]);
+        let mut in3 = Pin::new(&[]);
+        let mut in4 = Pin::new(&[]);
+
+        let mut motor = MotorDriver::new_split(
+            RetryPin::new(in1.clone(), NoopDelay::new(), 1, 100),
+            in2.clone(),
+            in3.clone(),
+            in4.clone(),
+            None::<Pin>,
+            None::<Pin>,
+        );
+
+        assert!(matches!(motor.a.forward(), Err(MotorDriverError::GpioError)));
+
+        in1.done();
+        in2.done();
+        in3.done();
+        in4.done();
+    }
+}
@@ -0,0 +1,161 @@
+use embedded_hal::delay::DelayNs;
+
+use crate::driver::{Breaks, MotorDriverError, MotorState, PwmMovement};
+
+/// A single entry of a [Sequence]: the state to command and how long to hold it for.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Step {
+    pub state: MotorState,
+    pub ms: u32,
+}
+
+/// A queue of motor commands with a fixed capacity of `N` steps, stored inline so it needs no allocator.
+///
+/// # Example
+///
+/// ```ignore
+/// let mut sequence = Sequence::<8>::new();
+///
+/// sequence.push(MotorState::Forward(80), 1000)?;
+/// sequence.push(MotorState::Stop, 200)?;
+/// sequence.push(MotorState::Reverse(50), 500)?;
+///
+/// sequence.run(&mut motor.a, &mut delay)?;
+/// ```
+#[derive(Debug, Clone)]
+pub struct Sequence<const N: usize> {
+    steps: [Step; N],
+    len: usize,
+}
+
+impl<const N: usize> Sequence<N> {
+    pub fn new() -> Self {
+        Self {
+            steps: [Step::default(); N],
+            len: 0,
+        }
+    }
+
+    /// Appends a step holding `state` for `ms` milliseconds.
+    ///
+    /// Returns [MotorDriverError::CapacityExceeded] if the sequence already holds `N` steps.
+    pub fn push(&mut self, state: MotorState, ms: u32) -> Result<(), MotorDriverError> {
+        let step = self.steps.get_mut(self.len).ok_or(MotorDriverError::CapacityExceeded)?;
+
+        *step = Step { state, ms };
+        self.len += 1;
+
+        Ok(())
+    }
+
+    /// Removes every step.
+    pub fn clear(&mut self) {
+        self.len = 0;
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Returns the queued steps, in order.
+    pub fn steps(&self) -> &[Step] {
+        &self.steps[..self.len]
+    }
+
+    /// Commands each step on `motor` in order, waiting its duration before moving on to the next one.
+    ///
+    /// The motor is left in the state of the last step.
+    #[must_use = "motor command result must be checked"]
+    pub fn run<MOTOR, DELAY>(&self, motor: &mut MOTOR, delay: &mut DELAY) -> Result<(), MotorDriverError>
+        where
+            MOTOR: PwmMovement + Breaks,
+            DELAY: DelayNs,
+    {
+        for step in self.steps() {
+            match step.state {
+                MotorState::Coast => motor.coast()?,
+                MotorState::Stop => motor.stop()?,
+                MotorState::Forward(percent) => motor.forward(percent)?,
+                MotorState::Reverse(percent) => motor.reverse(percent)?,
+            }
+
+            delay.delay_ms(step.ms);
+        }
+
+        Ok(())
+    }
+}
+
+impl<const N: usize> Default for Sequence<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use embedded_hal_mock::eh1::pwm::Mock as PwmPin;
+    use embedded_hal_mock::eh1::pwm::Transaction as PwmPinTransaction;
+
+    use crate::bridge::PwmBridge;
+    use crate::driver::{Decay, MotorDriverError, MotorState};
+    use crate::mock_delay::MockDelay;
+    use crate::sequence::Sequence;
+
+    #[test]
+    fn test_push_beyond_capacity_is_rejected() -> Result<(), MotorDriverError> {
+        let mut sequence = Sequence::<8>::new();
+
+        for _ in 0..8 {
+            sequence.push(MotorState::Coast, 10)?;
+        }
+
+        assert!(matches!(sequence.push(MotorState::Coast, 10), Err(MotorDriverError::CapacityExceeded)));
+        assert_eq!(sequence.len(), 8);
+
+        sequence.clear();
+
+        assert!(sequence.is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_steps_are_run_in_order() -> Result<(), MotorDriverError> {
+        let mut in1 = PwmPin::new(&[
+            PwmPinTransaction::max_duty_cycle(100),
+            PwmPinTransaction::set_duty_cycle(80),
+            PwmPinTransaction::set_duty_cycle(0),
+        ]);
+
+        let mut in2 = PwmPin::new(&[PwmPinTransaction::set_duty_cycle(0), PwmPinTransaction::set_duty_cycle(0)]);
+
+        let mut bridge = PwmBridge::new(in1.clone(), in2.clone(), 0);
+        let mut delay = MockDelay::new();
+        let mut sequence = Sequence::<2>::new();
+
+        bridge.set_decay(Decay::Fast);
+
+        sequence.push(MotorState::Forward(80), 1000)?;
+        sequence.push(MotorState::Coast, 200)?;
+        sequence.run(&mut bridge, &mut delay)?;
+
+        assert_eq!(delay.delays(), &[Duration::from_millis(1000), Duration::from_millis(200)]);
+        assert_eq!(bridge.state(), MotorState::Coast);
+
+        in1.done();
+        in2.done();
+
+        Ok(())
+    }
+}
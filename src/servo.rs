@@ -0,0 +1,176 @@
+use crate::driver::{Breaks, MotorDriverError, PwmMovement};
+use crate::pid::{Pid, PID_GAIN_SCALE};
+
+/// Reports the position of the output driven by a [Servo], e.g. a potentiometer read through an ADC.
+pub trait PositionSensor {
+    /// Returns the current position, from `0` to `100`.
+    fn position(&mut self) -> u8;
+}
+
+/// Turns a DC motor with position feedback into a servo using a [Pid] controller, proportional only
+/// unless [integral and derivative gains](Servo::set_gains) are set.
+///
+/// Driving the motor forward is expected to increase the position reported by the sensor.
+///
+/// # Example
+///
+/// ```ignore
+/// let mut servo = Servo::new(&mut motor.a, potentiometer);
+///
+/// servo.move_to(75)?;
+/// ```
+pub struct Servo<MOTOR, SENSOR> {
+    motor: MOTOR,
+    sensor: SENSOR,
+    target: u8,
+    tolerance: u8,
+    pid: Pid,
+}
+
+impl<MOTOR, SENSOR> Servo<MOTOR, SENSOR>
+    where
+        MOTOR: PwmMovement + Breaks,
+        SENSOR: PositionSensor,
+{
+    pub fn new(motor: MOTOR, sensor: SENSOR) -> Self {
+        Self {
+            motor,
+            sensor,
+            target: 0,
+            tolerance: 1,
+            pid: Pid::new(10 * PID_GAIN_SCALE as u16, 0, 0),
+        }
+    }
+
+    /// Sets how far from the target the position may be to be considered reached.
+    pub fn set_tolerance(&mut self, tolerance: u8) {
+        self.tolerance = tolerance;
+    }
+
+    /// Sets the speed percentage applied per unit of position error, the result is capped at 100%.
+    ///
+    /// This makes the controller proportional only, see [Servo::set_gains] for the full PID.
+    pub fn set_gain(&mut self, gain: u8) {
+        self.pid.set_gains(gain as u16 * PID_GAIN_SCALE as u16, 0, 0);
+    }
+
+    /// Sets the gains of the [Pid] controller, in hundredths of a percentage per unit of error, e.g. an
+    /// integral gain overcomes the friction which keeps a proportional controller short of the target.
+    pub fn set_gains(&mut self, kp: u16, ki: u16, kd: u16) {
+        self.pid.set_gains(kp, ki, kd);
+    }
+
+    /// Sets a new target without blocking, see [Servo::update].
+    pub fn set_target(&mut self, target: u8) {
+        if target != self.target {
+            self.pid.reset();
+        }
+
+        self.target = target;
+    }
+
+    pub fn target(&self) -> u8 {
+        self.target
+    }
+
+    /// Runs a single step of the controller, driving the motor towards the target or coasting it once
+    /// within tolerance. Returns `true` when the target has been reached.
+    #[must_use = "motor command result must be checked"]
+    pub fn update(&mut self) -> Result<bool, MotorDriverError> {
+        let error = self.target as i32 - self.sensor.position() as i32;
+
+        if error.unsigned_abs() <= self.tolerance as u32 {
+            self.pid.reset();
+            self.motor.coast()?;
+
+            return Ok(true);
+        }
+
+        let output = self.pid.update(error);
+
+        if output >= 0 {
+            self.motor.forward(output.unsigned_abs())?;
+        } else {
+            self.motor.reverse(output.unsigned_abs())?;
+        }
+
+        Ok(false)
+    }
+
+    /// Drives the motor until the sensor reads within tolerance of `target`, then coasts.
+    ///
+    /// This blocks until the target is reached, use [Servo::set_target] and [Servo::update] from a
+    /// control loop if the mechanism may be unable to reach it.
+    #[must_use = "motor command result must be checked"]
+    pub fn move_to(&mut self, target: u8) -> Result<(), MotorDriverError> {
+        self.set_target(target);
+
+        while !self.update()? {}
+
+        Ok(())
+    }
+
+    /// Releases the motor and sensor.
+    pub fn release(self) -> (MOTOR, SENSOR) {
+        (self.motor, self.sensor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use embedded_hal_mock::eh1::pin::Mock as Pin;
+    use embedded_hal_mock::eh1::pwm::Mock as PwmPin;
+    use embedded_hal_mock::eh1::pwm::Transaction as PwmPinTransaction;
+
+    use crate::driver::{Decay, MotorDriver, MotorDriverError};
+    use crate::servo::{PositionSensor, Servo};
+
+    struct Readings(Vec<u8>);
+
+    impl PositionSensor for Readings {
+        fn position(&mut self) -> u8 {
+            self.0.remove(0)
+        }
+    }
+
+    #[test]
+    fn test_moves_towards_the_target_and_coasts_within_tolerance() -> Result<(), MotorDriverError> {
+        let mut in1 = PwmPin::new(&[
+            PwmPinTransaction::max_duty_cycle(100),
+            PwmPinTransaction::set_duty_cycle(100),
+            PwmPinTransaction::max_duty_cycle(100),
+            PwmPinTransaction::set_duty_cycle(75),
+            PwmPinTransaction::set_duty_cycle(0),
+            PwmPinTransaction::set_duty_cycle(0),
+        ]);
+
+        let mut in2 = PwmPin::new(&[
+            PwmPinTransaction::set_duty_cycle(0),
+            PwmPinTransaction::set_duty_cycle(0),
+            PwmPinTransaction::max_duty_cycle(100),
+            PwmPinTransaction::set_duty_cycle(10),
+            PwmPinTransaction::set_duty_cycle(0),
+        ]);
+
+        let mut in3 = PwmPin::new(&[]);
+        let mut in4 = PwmPin::new(&[]);
+
+        let mut motor = MotorDriver::new_pwm_split(
+            in1.clone(), in2.clone(), in3.clone(), in4.clone(), None::<Pin>, None::<Pin>,
+        );
+
+        motor.set_decay(Decay::Fast);
+
+        let mut servo = Servo::new(&mut motor.a, Readings(vec![50, 60, 77, 74]));
+
+        servo.set_gain(5);
+        servo.move_to(75)?;
+
+        in1.done();
+        in2.done();
+        in3.done();
+        in4.done();
+
+        Ok(())
+    }
+}
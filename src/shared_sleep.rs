@@ -0,0 +1,134 @@
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use embedded_hal::digital::{self, OutputPin, PinState, StatefulOutputPin};
+
+/// Shares a single nSLEEP pin between several [MotorDriver](crate::MotorDriver)s whose devices sit on
+/// the same sleep net, so they can all sleep and wake up through it.
+///
+/// Each clone is handed to a driver as its sleep pin. Writes are serialized behind a mutex and the level
+/// last written is tracked for all clones, so [MotorDriver::read_awake](crate::MotorDriver::read_awake)
+/// reports the state of the net on every driver, whichever one drove it last. The pin is assumed low,
+/// i.e. asleep, until first written.
+///
+/// # Example
+///
+/// ```no_run
+/// use drv8833_driver::{MotorDriver, SharedSleep};
+/// use embedded_hal_mock::eh1::pin::Mock as Pin;
+///
+/// let (in1, in2, in3, in4) = (Pin::new(&[]), Pin::new(&[]), Pin::new(&[]), Pin::new(&[]));
+/// let (in5, in6, in7, in8) = (Pin::new(&[]), Pin::new(&[]), Pin::new(&[]), Pin::new(&[]));
+///
+/// let sleep = SharedSleep::new(Pin::new(&[]));
+///
+/// let mut left = MotorDriver::new_split(in1, in2, in3, in4, Some(sleep.clone()), None::<Pin>);
+/// let mut right = MotorDriver::new_split(in5, in6, in7, in8, Some(sleep), None::<Pin>);
+///
+/// left.wakeup()?;
+///
+/// assert!(right.read_awake()?);
+/// # Ok::<(), drv8833_driver::MotorDriverError>(())
+/// ```
+pub struct SharedSleep<PIN> {
+    shared: Arc<Mutex<(PIN, PinState)>>,
+}
+
+impl<PIN> SharedSleep<PIN> {
+    pub fn new(pin: PIN) -> Self {
+        Self {
+            shared: Arc::new(Mutex::new((pin, PinState::Low))),
+        }
+    }
+
+    /// Returns whether the shared pin was last driven high.
+    pub fn is_awake(&self) -> bool {
+        self.lock().1 == PinState::High
+    }
+
+    /// A panic while holding the lock can't leave the pin half written, so a poisoned lock is recovered.
+    fn lock(&self) -> MutexGuard<'_, (PIN, PinState)> {
+        self.shared.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl<PIN> Clone for SharedSleep<PIN> {
+    fn clone(&self) -> Self {
+        Self {
+            shared: self.shared.clone(),
+        }
+    }
+}
+
+impl<PIN: digital::ErrorType> digital::ErrorType for SharedSleep<PIN> {
+    type Error = PIN::Error;
+}
+
+impl<PIN: OutputPin> OutputPin for SharedSleep<PIN> {
+    fn set_low(&mut self) -> Result<(), Self::Error> {
+        self.set_state(PinState::Low)
+    }
+
+    fn set_high(&mut self) -> Result<(), Self::Error> {
+        self.set_state(PinState::High)
+    }
+
+    fn set_state(&mut self, state: PinState) -> Result<(), Self::Error> {
+        let mut shared = self.lock();
+
+        shared.0.set_state(state)?;
+        shared.1 = state;
+
+        Ok(())
+    }
+}
+
+impl<PIN: OutputPin> StatefulOutputPin for SharedSleep<PIN> {
+    fn is_set_high(&mut self) -> Result<bool, Self::Error> {
+        Ok(self.is_awake())
+    }
+
+    fn is_set_low(&mut self) -> Result<bool, Self::Error> {
+        Ok(!self.is_awake())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use embedded_hal_mock::eh1::pin::Mock as Pin;
+    use embedded_hal_mock::eh1::pin::State::{High, Low};
+    use embedded_hal_mock::eh1::pin::Transaction;
+
+    use crate::driver::{MotorDriver, MotorDriverError};
+    use crate::shared_sleep::SharedSleep;
+
+    #[test]
+    fn test_drivers_sharing_a_sleep_pin_agree_on_its_state() -> Result<(), MotorDriverError> {
+        let mut sleep = Pin::new(&[Transaction::set(High), Transaction::set(Low)]);
+        let mut pin = Pin::new(&[]);
+
+        let shared = SharedSleep::new(sleep.clone());
+
+        let mut left = MotorDriver::new_split(
+            pin.clone(), pin.clone(), pin.clone(), pin.clone(), Some(shared.clone()), None::<Pin>,
+        );
+
+        let mut right = MotorDriver::new_split(
+            pin.clone(), pin.clone(), pin.clone(), pin.clone(), Some(shared), None::<Pin>,
+        );
+
+        left.wakeup()?;
+
+        assert!(left.read_awake()?);
+        assert!(right.read_awake()?);
+
+        right.sleep()?;
+
+        assert!(!left.read_awake()?);
+        assert!(!right.read_awake()?);
+
+        sleep.done();
+        pin.done();
+
+        Ok(())
+    }
+}
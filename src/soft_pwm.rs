@@ -0,0 +1,149 @@
+use core::cell::{Cell, RefCell};
+
+use embedded_hal::delay::DelayNs;
+use embedded_hal::digital::OutputPin;
+use embedded_hal::pwm::{ErrorKind, ErrorType, SetDutyCycle};
+
+use crate::driver::MotorDriverError;
+
+/// Resolution of the software PWM, duty values range from `0` to this value.
+const MAX_DUTY: u16 = 100;
+
+/// Bit-bangs a PWM signal on a plain [OutputPin], such as the pins exposed by I/O expanders
+/// (e.g. the `port-expander` crate), so it can be used by the PWM modes which require [SetDutyCycle].
+///
+/// The adapter is shared by reference: pass `&adapter` to the driver and keep calling
+/// [SoftPwmAdapter::run_cycle] from your main loop to generate the waveform. Fully on and fully
+/// off duty values are applied to the pin immediately and don't need to be cycled.
+///
+/// # Example
+///
+/// ```ignore
+/// let in1 = SoftPwmAdapter::new(expander_pins.p0, delay.clone(), 1_000);
+/// let in2 = SoftPwmAdapter::new(expander_pins.p1, delay.clone(), 1_000);
+/// let in3 = SoftPwmAdapter::new(expander_pins.p2, delay.clone(), 1_000);
+/// let in4 = SoftPwmAdapter::new(expander_pins.p3, delay.clone(), 1_000);
+///
+/// let mut motor = MotorDriver::new_pwm_split(&in1, &in2, &in3, &in4, None::<Pin>, None::<Pin>);
+///
+/// motor.a.forward(50)?;
+///
+/// loop {
+///     in1.run_cycle()?;
+/// }
+/// ```
+pub struct SoftPwmAdapter<PIN, DELAY> {
+    pin: RefCell<PIN>,
+    delay: RefCell<DELAY>,
+    period_us: u32,
+    duty: Cell<u16>,
+}
+
+impl<PIN, DELAY> SoftPwmAdapter<PIN, DELAY>
+    where
+        PIN: OutputPin,
+        DELAY: DelayNs,
+{
+    /// Creates a new adapter generating a signal with the given period in microseconds.
+    pub fn new(pin: PIN, delay: DELAY, period_us: u32) -> Self {
+        Self {
+            pin: RefCell::new(pin),
+            delay: RefCell::new(delay),
+            period_us,
+            duty: Cell::new(0),
+        }
+    }
+
+    /// Generates a single period of the PWM signal, blocking for its whole duration.
+    pub fn run_cycle(&self) -> Result<(), MotorDriverError> {
+        let mut pin = self.pin.borrow_mut();
+        let mut delay = self.delay.borrow_mut();
+
+        let duty = self.duty.get();
+        let on_us = (self.period_us as u64 * duty as u64 / MAX_DUTY as u64) as u32;
+
+        if on_us > 0 {
+            pin.set_high().map_err(|_| MotorDriverError::GpioError)?;
+            delay.delay_us(on_us);
+        }
+
+        if on_us < self.period_us {
+            pin.set_low().map_err(|_| MotorDriverError::GpioError)?;
+            delay.delay_us(self.period_us - on_us);
+        }
+
+        Ok(())
+    }
+
+    /// Releases the pin and delay provider.
+    pub fn release(self) -> (PIN, DELAY) {
+        (self.pin.into_inner(), self.delay.into_inner())
+    }
+}
+
+impl<PIN, DELAY> ErrorType for &SoftPwmAdapter<PIN, DELAY> {
+    type Error = ErrorKind;
+}
+
+impl<PIN, DELAY> SetDutyCycle for &SoftPwmAdapter<PIN, DELAY>
+    where
+        PIN: OutputPin,
+        DELAY: DelayNs,
+{
+    fn max_duty_cycle(&self) -> u16 {
+        MAX_DUTY
+    }
+
+    fn set_duty_cycle(&mut self, duty: u16) -> Result<(), Self::Error> {
+        let duty = duty.min(MAX_DUTY);
+
+        self.duty.set(duty);
+
+        let result = match duty {
+            0 => self.pin.borrow_mut().set_low(),
+            MAX_DUTY => self.pin.borrow_mut().set_high(),
+            _ => Ok(()),
+        };
+
+        result.map_err(|_| ErrorKind::Other)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use embedded_hal_mock::eh1::delay::NoopDelay;
+    use embedded_hal_mock::eh1::pin::Mock as Pin;
+    use embedded_hal_mock::eh1::pin::State::{High, Low};
+    use embedded_hal_mock::eh1::pin::Transaction;
+
+    use crate::driver::{Decay, MotorDriver, MotorDriverError, PwmMovement};
+    use crate::soft_pwm::SoftPwmAdapter;
+
+    #[test]
+    fn test_output_pins_can_drive_a_pwm_bridge() -> Result<(), MotorDriverError> {
+        let mut in1 = Pin::new(&[Transaction::set(High), Transaction::set(Low)]);
+        let mut in2 = Pin::new(&[Transaction::set(Low)]);
+        let mut in3 = Pin::new(&[]);
+        let mut in4 = Pin::new(&[]);
+
+        let pwm1 = SoftPwmAdapter::new(in1.clone(), NoopDelay::new(), 1_000);
+        let pwm2 = SoftPwmAdapter::new(in2.clone(), NoopDelay::new(), 1_000);
+        let pwm3 = SoftPwmAdapter::new(in3.clone(), NoopDelay::new(), 1_000);
+        let pwm4 = SoftPwmAdapter::new(in4.clone(), NoopDelay::new(), 1_000);
+
+        let mut motor = MotorDriver::new_pwm_split(&pwm1, &pwm2, &pwm3, &pwm4, None::<Pin>, None::<Pin>);
+
+        motor.set_decay(Decay::Fast);
+
+        motor.a.forward(50)?;
+
+        pwm1.run_cycle()?;
+
+        in1.done();
+        in2.done();
+        in3.done();
+        in4.done();
+
+        Ok(())
+    }
+}
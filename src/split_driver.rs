@@ -1,7 +1,7 @@
-use embedded_hal::digital::OutputPin;
+use embedded_hal::digital::{OutputPin, PinState};
 
 use crate::bridge::Bridge;
-use crate::driver::Driver;
+use crate::driver::{Breaks, BridgeStates, CurrentLoad, Driver, MotorDriverError, MotorState};
 
 pub struct SplitDriver<IN1, IN2, IN3, IN4>
     where
@@ -21,6 +21,20 @@ impl<IN1: OutputPin, IN2: OutputPin, IN3: OutputPin, IN4: OutputPin> SplitDriver
             b: Bridge::new(in3, in4),
         }
     }
+
+    /// Returns the levels last written to IN1, IN2, IN3 and IN4, derived from each bridge's tracked state
+    /// rather than read back from the pins.
+    pub fn pin_states(&self) -> [PinState; 4] {
+        let [in1, in2] = self.a.state().pin_states();
+        let [in3, in4] = self.b.state().pin_states();
+
+        [in1, in2, in3, in4]
+    }
+
+    /// Returns the last state successfully commanded to bridge A and B, e.g. to log both at once.
+    pub fn states(&self) -> (MotorState, MotorState) {
+        (self.a.state(), self.b.state())
+    }
 }
 
 impl<IN1, IN2, IN3, IN4> Driver for SplitDriver<IN1, IN2, IN3, IN4>
@@ -31,28 +45,81 @@ impl<IN1, IN2, IN3, IN4> Driver for SplitDriver<IN1, IN2, IN3, IN4>
         IN4: OutputPin,
 {}
 
+impl<IN1, IN2, IN3, IN4> Breaks for SplitDriver<IN1, IN2, IN3, IN4>
+    where
+        IN1: OutputPin,
+        IN2: OutputPin,
+        IN3: OutputPin,
+        IN4: OutputPin,
+{
+    fn coast(&mut self) -> Result<(), MotorDriverError> {
+        self.a.coast()?;
+        self.b.coast()?;
+
+        Ok(())
+    }
+
+    fn stop(&mut self) -> Result<(), MotorDriverError> {
+        self.a.stop()?;
+        self.b.stop()?;
+
+        Ok(())
+    }
+}
+
+impl<IN1, IN2, IN3, IN4> CurrentLoad for SplitDriver<IN1, IN2, IN3, IN4>
+    where
+        IN1: OutputPin,
+        IN2: OutputPin,
+        IN3: OutputPin,
+        IN4: OutputPin,
+{
+    fn load_percent(&self) -> u16 {
+        self.a.state().percent() as u16 + self.b.state().percent() as u16
+    }
+}
+
+impl<IN1, IN2, IN3, IN4> BridgeStates for SplitDriver<IN1, IN2, IN3, IN4>
+    where
+        IN1: OutputPin,
+        IN2: OutputPin,
+        IN3: OutputPin,
+        IN4: OutputPin,
+{
+    fn bridge_states(&self) -> [MotorState; 2] {
+        [self.a.state(), self.b.state()]
+    }
+
+    fn restore_bridge_states(&mut self, states: [MotorState; 2]) -> Result<(), MotorDriverError> {
+        self.a.set_state(states[0])?;
+        self.b.set_state(states[1])
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use std::io::ErrorKind;
+    use std::time::Duration;
+
+    use embedded_hal::digital::OutputPin;
     use embedded_hal_mock::eh1::pin::Mock as Pin;
     use embedded_hal_mock::eh1::pin::State::{High, Low};
     use embedded_hal_mock::eh1::pin::Transaction;
     use embedded_hal_mock::eh1::pwm::Mock as PwmPin;
     use embedded_hal_mock::eh1::pwm::Transaction as PwmPinTransaction;
+    use embedded_hal_mock::eh1::MockError;
 
-    use crate::driver::{Breaks, MotorDriver, MotorDriverError, Movement};
+    use crate::driver::{Breaks, Direction, MotorDriver, MotorDriverError, MotorState, Movement, PinInitError, SleepBehavior};
+    use crate::mock_delay::MockDelay;
+    use crate::mock_pins::MockPins;
+    use crate::split_driver::SplitDriver;
 
     #[test]
     fn it_can_drive_each_bridge_independently() -> Result<(), MotorDriverError> {
-        let mut in1 = Pin::new(&[Transaction::set(High), Transaction::set(Low)]);
-        let mut in2 = Pin::new(&[Transaction::set(Low), Transaction::set(Low)]);
-        let mut in3 = Pin::new(&[Transaction::set(Low), Transaction::set(High)]);
-        let mut in4 = Pin::new(&[Transaction::set(High), Transaction::set(High)]);
+        let mut pins = MockPins::new([&[High, Low], &[Low, Low], &[Low, High], &[High, High]]).with_sleep(&[High, Low]);
+        let [in1, in2, in3, in4] = pins.ins();
 
-        let mut sleep = Pin::new(&[Transaction::set(High), Transaction::set(Low)]);
-
-        let mut motor = MotorDriver::new_split(
-            in1.clone(), in2.clone(), in3.clone(), in4.clone(), Some(sleep.clone()), None::<Pin>,
-        );
+        let mut motor = MotorDriver::new_split(in1, in2, in3, in4, Some(pins.sleep()), None::<Pin>);
 
         motor.wakeup()?;
 
@@ -64,12 +131,7 @@ mod tests {
 
         motor.sleep()?;
 
-        in1.done();
-        in2.done();
-        in3.done();
-        in4.done();
-
-        sleep.done();
+        pins.done();
 
         Ok(())
     }
@@ -91,6 +153,8 @@ mod tests {
 
         motor.set_duty_cycle(50)?;
 
+        assert_eq!(motor.duty_cycle_percent(), 50);
+
         motor.a.forward()?;
         motor.b.reverse()?;
         motor.a.coast()?;
@@ -105,4 +169,203 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_coast_then_sleep_coasts_both_bridges_before_sleeping() -> Result<(), MotorDriverError> {
+        let mut pins = MockPins::new([&[Low], &[Low], &[Low], &[Low]]).with_sleep(&[Low]);
+        let [in1, in2, in3, in4] = pins.ins();
+
+        let mut motor = MotorDriver::new_split(in1, in2, in3, in4, Some(pins.sleep()), None::<Pin>);
+
+        motor.set_sleep_behavior(SleepBehavior::CoastThenSleep);
+        motor.sleep()?;
+
+        pins.done();
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_it_can_wrap_a_prebuilt_driver() -> Result<(), MotorDriverError> {
+        let mut in1 = Pin::new(&[Transaction::set(Low)]);
+        let mut in2 = Pin::new(&[Transaction::set(High)]);
+        let mut in3 = Pin::new(&[]);
+        let mut in4 = Pin::new(&[]);
+
+        let mut sleep = Pin::new(&[Transaction::set(High)]);
+
+        let mut split = SplitDriver::new(in1.clone(), in2.clone(), in3.clone(), in4.clone());
+
+        split.a.set_clockwise_direction(Direction::Reverse);
+
+        let mut motor = MotorDriver::from_driver(split, Some(sleep.clone()), None::<Pin>);
+
+        motor.wakeup()?;
+        motor.a.clockwise()?;
+
+        in1.done();
+        in2.done();
+        in3.done();
+        in4.done();
+
+        sleep.done();
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_into_driver_returns_the_owned_split_driver() -> Result<(), MotorDriverError> {
+        let mut in1 = Pin::new(&[Transaction::set(High)]);
+        let mut in2 = Pin::new(&[Transaction::set(Low)]);
+        let mut in3 = Pin::new(&[]);
+        let mut in4 = Pin::new(&[]);
+
+        let motor = MotorDriver::new_split(
+            in1.clone(), in2.clone(), in3.clone(), in4.clone(), None::<Pin>, None::<Pin>,
+        );
+
+        let mut split: SplitDriver<_, _, _, _> = motor.into_driver();
+
+        split.a.forward()?;
+
+        assert_eq!(split.states(), (MotorState::Forward(100), MotorState::Coast));
+
+        in1.done();
+        in2.done();
+        in3.done();
+        in4.done();
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_bridge_b_pins_can_be_freed_for_other_uses() -> Result<(), MotorDriverError> {
+        let mut in1 = Pin::new(&[Transaction::set(High)]);
+        let mut in2 = Pin::new(&[Transaction::set(Low)]);
+        let mut in3 = Pin::new(&[Transaction::set(High)]);
+        let mut in4 = Pin::new(&[]);
+
+        let mut sleep = Pin::new(&[Transaction::set(High)]);
+
+        let motor = MotorDriver::new_split(
+            in1.clone(), in2.clone(), in3.clone(), in4.clone(), Some(sleep.clone()), None::<Pin>,
+        );
+
+        let (mut motor, mut led, _) = motor.take_bridge_b();
+
+        motor.wakeup()?;
+        motor.forward()?;
+
+        assert_eq!(motor.state(), MotorState::Forward(100));
+
+        led.set_high().map_err(|_| MotorDriverError::GpioError)?;
+
+        in1.done();
+        in2.done();
+        in3.done();
+        in4.done();
+
+        sleep.done();
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_deep_sleep_restores_the_bridges_on_wakeup() -> Result<(), MotorDriverError> {
+        let mut in1 = Pin::new(&[Transaction::set(High), Transaction::set(Low), Transaction::set(High)]);
+        let mut in2 = Pin::new(&[Transaction::set(Low), Transaction::set(Low), Transaction::set(Low)]);
+        let mut in3 = Pin::new(&[Transaction::set(Low), Transaction::set(Low), Transaction::set(Low)]);
+        let mut in4 = Pin::new(&[Transaction::set(High), Transaction::set(Low), Transaction::set(High)]);
+        let mut sleep = Pin::new(&[Transaction::set(Low), Transaction::set(High)]);
+
+        let mut motor = MotorDriver::new_split(
+            in1.clone(), in2.clone(), in3.clone(), in4.clone(), Some(sleep.clone()), None::<Pin>,
+        );
+
+        motor.a.forward()?;
+        motor.b.reverse()?;
+
+        motor.deep_sleep()?;
+
+        assert_eq!(motor.a.state(), MotorState::Coast);
+        assert!(!motor.is_awake());
+
+        motor.wakeup_restore()?;
+
+        assert_eq!(motor.a.state(), MotorState::Forward(100));
+        assert_eq!(motor.b.state(), MotorState::Reverse(100));
+
+        in1.done();
+        in2.done();
+        in3.done();
+        in4.done();
+
+        sleep.done();
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_sleep_without_a_sleep_pin_touches_no_pins() -> Result<(), MotorDriverError> {
+        let mut pin = Pin::new(&[]);
+
+        let mut motor = MotorDriver::new_split(
+            pin.clone(), pin.clone(), pin.clone(), pin.clone(), None::<Pin>, None::<Pin>,
+        );
+
+        motor.sleep()?;
+        motor.wakeup()?;
+
+        assert!(motor.is_awake());
+
+        motor.set_strict_sleep(true);
+
+        assert!(matches!(motor.sleep(), Err(MotorDriverError::NoSleepControl)));
+        assert!(matches!(motor.wakeup(), Err(MotorDriverError::NoSleepControl)));
+
+        pin.done();
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_wakeup_and_wait_waits_for_the_charge_pump() -> Result<(), MotorDriverError> {
+        let mut pins = MockPins::new([&[], &[], &[], &[]]).with_sleep(&[High, High]);
+        let [in1, in2, in3, in4] = pins.ins();
+
+        let mut motor = MotorDriver::new_split(in1, in2, in3, in4, Some(pins.sleep()), None::<Pin>);
+        let mut delay = MockDelay::new();
+
+        motor.wakeup_and_wait(&mut delay)?;
+        motor.set_wake_settle_us(250);
+        motor.wakeup_and_wait(&mut delay)?;
+
+        assert!(motor.is_awake());
+        assert_eq!(delay.delays(), &[Duration::from_millis(1), Duration::from_micros(250)]);
+
+        pins.done();
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_fallible_constructor_reports_every_failing_pin() {
+        let mut in1 = Pin::new(&[Transaction::set(Low)]);
+        let mut in2 = Pin::new(&[Transaction::set(Low).with_error(MockError::Io(ErrorKind::Other))]);
+        let mut in3 = Pin::new(&[Transaction::set(Low)]);
+        let mut in4 = Pin::new(&[Transaction::set(Low).with_error(MockError::Io(ErrorKind::Other))]);
+
+        let result = MotorDriver::try_new_split(in1.clone(), in2.clone(), in3.clone(), in4.clone(), None::<Pin>, None::<Pin>);
+
+        let error = result.err().unwrap();
+
+        assert_eq!(error, PinInitError { failed: [false, true, false, true] });
+        assert_eq!(error.failed_pins().collect::<Vec<_>>(), [2, 4]);
+        assert_eq!(error.to_string(), "failed to initialize IN2 IN4");
+
+        in1.done();
+        in2.done();
+        in3.done();
+        in4.done();
+    }
 }
@@ -0,0 +1,313 @@
+use embedded_hal::digital::OutputPin;
+
+use crate::bridge::Bridge;
+use crate::driver::{Breaks, Direction, Driver, MotorDriverError, Movement};
+
+/// Coil polarities of bridge A and B for each phase of the full step (two phase on) sequence.
+const PHASES: [(Direction, Direction); 4] = [
+    (Direction::Forward, Direction::Forward),
+    (Direction::Reverse, Direction::Forward),
+    (Direction::Reverse, Direction::Reverse),
+    (Direction::Forward, Direction::Reverse),
+];
+
+/// Drives a bipolar stepper motor in full steps, with one coil wired to each bridge, while keeping
+/// track of its position in steps.
+pub struct StepperDriver<IN1, IN2, IN3, IN4>
+    where
+        IN1: OutputPin,
+        IN2: OutputPin,
+        IN3: OutputPin,
+        IN4: OutputPin,
+{
+    a: Bridge<IN1, IN2>,
+    b: Bridge<IN3, IN4>,
+    phase: usize,
+    position: i32,
+    steps_per_rev: Option<u32>,
+}
+
+impl<IN1, IN2, IN3, IN4> StepperDriver<IN1, IN2, IN3, IN4>
+    where
+        IN1: OutputPin,
+        IN2: OutputPin,
+        IN3: OutputPin,
+        IN4: OutputPin,
+{
+    pub fn new(in1: IN1, in2: IN2, in3: IN3, in4: IN4) -> Self {
+        Self {
+            a: Bridge::new(in1, in2),
+            b: Bridge::new(in3, in4),
+            phase: 0,
+            position: 0,
+            steps_per_rev: None,
+        }
+    }
+
+    /// Advances the motor by a single step.
+    #[must_use = "motor command result must be checked"]
+    pub fn step_forward(&mut self) -> Result<(), MotorDriverError> {
+        self.step(Direction::Forward)
+    }
+
+    /// Moves the motor back by a single step.
+    #[must_use = "motor command result must be checked"]
+    pub fn step_reverse(&mut self) -> Result<(), MotorDriverError> {
+        self.step(Direction::Reverse)
+    }
+
+    /// De-energizes both coils by coasting the bridges, e.g. to save power and keep the driver cool while the
+    /// axis is idle. The position is kept, but the shaft is no longer held and may be moved by external forces.
+    #[must_use = "motor command result must be checked"]
+    pub fn release(&mut self) -> Result<(), MotorDriverError> {
+        self.a.coast()?;
+        self.b.coast()?;
+
+        Ok(())
+    }
+
+    /// Energizes the coils again with the pattern of the current step, after a [release](StepperDriver::release).
+    #[must_use = "motor command result must be checked"]
+    pub fn hold(&mut self) -> Result<(), MotorDriverError> {
+        self.apply_phase(self.phase)
+    }
+
+    /// Returns the position in steps since the driver was created, or since it was last [set](StepperDriver::set_position).
+    ///
+    /// Without a [wrap](StepperDriver::set_position_wrap) the counter saturates at the `i32` bounds
+    /// instead of overflowing.
+    pub fn position(&self) -> i32 {
+        self.position
+    }
+
+    /// Zeroes the position counter without moving the motor, e.g. once a homing routine hits the limit switch.
+    pub fn reset_position(&mut self) {
+        self.set_position(0);
+    }
+
+    /// Sets the position counter without moving the motor, e.g. to the known position of a limit switch.
+    /// With a [wrap](StepperDriver::set_position_wrap) the position is brought within a single revolution.
+    pub fn set_position(&mut self, position: i32) {
+        self.position = match self.steps_per_rev {
+            Some(steps_per_rev) => position.rem_euclid(steps_per_rev as i32),
+            None => position,
+        };
+    }
+
+    /// Makes the position counter wrap around every `steps_per_rev` steps, so it always stays within
+    /// a single revolution and can never overflow, no matter how long the motor runs.
+    ///
+    /// Returns [MotorDriverError::InvalidRange] if `steps_per_rev` is zero or above `i32::MAX`.
+    pub fn set_position_wrap(&mut self, steps_per_rev: u32) -> Result<(), MotorDriverError> {
+        if steps_per_rev == 0 || steps_per_rev > i32::MAX as u32 {
+            return Err(MotorDriverError::InvalidRange);
+        }
+
+        self.steps_per_rev = Some(steps_per_rev);
+        self.position = self.position.rem_euclid(steps_per_rev as i32);
+
+        Ok(())
+    }
+
+    /// Returns the position within the current revolution, from `0` to `steps_per_rev - 1`, or `None`
+    /// if no [wrap](StepperDriver::set_position_wrap) is configured.
+    pub fn position_mod(&self) -> Option<u32> {
+        self.steps_per_rev
+            .map(|steps_per_rev| self.position.rem_euclid(steps_per_rev as i32) as u32)
+    }
+
+    fn step(&mut self, direction: Direction) -> Result<(), MotorDriverError> {
+        let (phase, delta) = match direction {
+            Direction::Forward => ((self.phase + 1) % PHASES.len(), 1),
+            Direction::Reverse => ((self.phase + PHASES.len() - 1) % PHASES.len(), -1),
+        };
+
+        self.apply_phase(phase)?;
+
+        self.phase = phase;
+        self.position = match self.steps_per_rev {
+            Some(steps_per_rev) => (self.position + delta).rem_euclid(steps_per_rev as i32),
+            None => self.position.saturating_add(delta),
+        };
+
+        Ok(())
+    }
+
+    fn apply_phase(&mut self, phase: usize) -> Result<(), MotorDriverError> {
+        let (a, b) = PHASES[phase];
+
+        match a {
+            Direction::Forward => self.a.forward()?,
+            Direction::Reverse => self.a.reverse()?,
+        }
+
+        match b {
+            Direction::Forward => self.b.forward()?,
+            Direction::Reverse => self.b.reverse()?,
+        }
+
+        Ok(())
+    }
+}
+
+impl<IN1, IN2, IN3, IN4> Driver for StepperDriver<IN1, IN2, IN3, IN4>
+    where
+        IN1: OutputPin,
+        IN2: OutputPin,
+        IN3: OutputPin,
+        IN4: OutputPin,
+{}
+
+impl<IN1, IN2, IN3, IN4> Breaks for StepperDriver<IN1, IN2, IN3, IN4>
+    where
+        IN1: OutputPin,
+        IN2: OutputPin,
+        IN3: OutputPin,
+        IN4: OutputPin,
+{
+    fn coast(&mut self) -> Result<(), MotorDriverError> {
+        self.a.coast()?;
+        self.b.coast()?;
+
+        Ok(())
+    }
+
+    fn stop(&mut self) -> Result<(), MotorDriverError> {
+        self.a.stop()?;
+        self.b.stop()?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use embedded_hal_mock::eh1::pin::Mock as Pin;
+    use embedded_hal_mock::eh1::pin::State::{High, Low};
+    use embedded_hal_mock::eh1::pin::Transaction;
+
+    use crate::driver::{MotorDriver, MotorDriverError};
+
+    #[test]
+    fn test_steps_through_the_full_step_sequence() -> Result<(), MotorDriverError> {
+        let mut in1 = Pin::new(&[Transaction::set(Low)]);
+        let mut in2 = Pin::new(&[Transaction::set(High)]);
+        let mut in3 = Pin::new(&[Transaction::set(High), Transaction::set(Low), Transaction::set(High)]);
+        let mut in4 = Pin::new(&[Transaction::set(Low), Transaction::set(High), Transaction::set(Low)]);
+
+        let mut motor = MotorDriver::new_stepper(
+            in1.clone(), in2.clone(), in3.clone(), in4.clone(), None::<Pin>, None::<Pin>,
+        );
+
+        motor.step_forward()?;
+        motor.step_forward()?;
+        motor.step_reverse()?;
+
+        assert_eq!(motor.position(), 1);
+        assert_eq!(motor.position_mod(), None);
+
+        in1.done();
+        in2.done();
+        in3.done();
+        in4.done();
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_position_can_be_set_without_moving() -> Result<(), MotorDriverError> {
+        let mut in1 = Pin::new(&[Transaction::set(Low)]);
+        let mut in2 = Pin::new(&[Transaction::set(High)]);
+        let mut in3 = Pin::new(&[Transaction::set(High)]);
+        let mut in4 = Pin::new(&[Transaction::set(Low)]);
+
+        let mut motor = MotorDriver::new_stepper(
+            in1.clone(), in2.clone(), in3.clone(), in4.clone(), None::<Pin>, None::<Pin>,
+        );
+
+        motor.step_forward()?;
+        motor.reset_position();
+
+        assert_eq!(motor.position(), 0);
+
+        motor.set_position(-5);
+
+        assert_eq!(motor.position(), -5);
+
+        motor.set_position_wrap(4)?;
+        motor.set_position(9);
+
+        assert_eq!(motor.position(), 1);
+
+        in1.done();
+        in2.done();
+        in3.done();
+        in4.done();
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_release_de_energizes_the_coils_and_hold_restores_them() -> Result<(), MotorDriverError> {
+        let mut in1 = Pin::new(&[Transaction::set(Low), Transaction::set(Low), Transaction::set(Low)]);
+        let mut in2 = Pin::new(&[Transaction::set(High), Transaction::set(Low), Transaction::set(High)]);
+        let mut in3 = Pin::new(&[Transaction::set(High), Transaction::set(Low), Transaction::set(High)]);
+        let mut in4 = Pin::new(&[Transaction::set(Low), Transaction::set(Low), Transaction::set(Low)]);
+
+        let mut motor = MotorDriver::new_stepper(
+            in1.clone(), in2.clone(), in3.clone(), in4.clone(), None::<Pin>, None::<Pin>,
+        );
+
+        motor.step_forward()?;
+        motor.release()?;
+
+        assert_eq!(motor.position(), 1);
+
+        motor.hold()?;
+
+        assert_eq!(motor.position(), 1);
+
+        in1.done();
+        in2.done();
+        in3.done();
+        in4.done();
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_position_rolls_over_at_the_wrap_boundary() -> Result<(), MotorDriverError> {
+        let mut pin = Pin::new(&[
+            Transaction::set(Low),
+            Transaction::set(High),
+            Transaction::set(High),
+            Transaction::set(Low),
+            Transaction::set(Low),
+            Transaction::set(High),
+            Transaction::set(High),
+            Transaction::set(Low),
+        ]);
+
+        let mut motor = MotorDriver::new_stepper(
+            pin.clone(), pin.clone(), pin.clone(), pin.clone(), None::<Pin>, None::<Pin>,
+        );
+
+        assert!(matches!(motor.set_position_wrap(0), Err(MotorDriverError::InvalidRange)));
+
+        motor.set_position_wrap(2)?;
+
+        motor.step_forward()?;
+        assert_eq!(motor.position_mod(), Some(1));
+
+        motor.step_forward()?;
+        assert_eq!(motor.position_mod(), Some(0));
+
+        motor.step_reverse()?;
+        assert_eq!(motor.position_mod(), Some(1));
+        assert_eq!(motor.position(), 1);
+
+        pin.done();
+
+        Ok(())
+    }
+}
@@ -0,0 +1,43 @@
+use embassy_time::{Duration, Timer};
+use embedded_hal::pwm::SetDutyCycle;
+
+use crate::bridge::PwmBridge;
+use crate::driver::{Breaks, Direction, MotorDriverError, PwmMovement};
+
+/// Timed moves awaiting an [embassy_time::Timer], so async applications don't have to pass a delay
+/// provider around. Available with the `embassy-time` feature.
+impl<IN1: SetDutyCycle, IN2: SetDutyCycle> PwmBridge<IN1, IN2> {
+    /// Drives the motor in `direction` at `percent` for `duration` and then coasts it, the async
+    /// counterpart of [PwmBridge::pulse_us].
+    ///
+    /// If the drive command fails the motor is coasted without waiting.
+    pub async fn run_for(&mut self, direction: Direction, percent: u8, duration: Duration) -> Result<(), MotorDriverError> {
+        let result = match direction {
+            Direction::Forward => self.forward(percent),
+            Direction::Reverse => self.reverse(percent),
+        };
+
+        if result.is_ok() {
+            Timer::after(duration).await;
+        }
+
+        self.coast()?;
+
+        result
+    }
+
+    /// Drives the motor forward at `percent` for `duration` and then coasts it, see [PwmBridge::run_for].
+    ///
+    /// ```ignore
+    /// motor.a.forward_for(50, Duration::from_millis(500)).await?;
+    /// ```
+    pub async fn forward_for(&mut self, percent: u8, duration: Duration) -> Result<(), MotorDriverError> {
+        self.run_for(Direction::Forward, percent, duration).await
+    }
+
+    /// Reverse counterpart of [PwmBridge::forward_for].
+    pub async fn reverse_for(&mut self, percent: u8, duration: Duration) -> Result<(), MotorDriverError> {
+        self.run_for(Direction::Reverse, percent, duration).await
+    }
+}
+
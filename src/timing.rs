@@ -0,0 +1,186 @@
+use crate::driver::{Breaks, DynMotor, MotorDriverError, Movement, PwmMovement};
+
+/// A monotonic time source used by [Instrumented] to measure how long each command takes.
+pub trait Clock {
+    /// Returns the current tick, which must never decrease. The unit is up to the implementation,
+    /// e.g. microseconds or timer ticks, and is the unit reported by [CommandTiming].
+    fn now(&mut self) -> u64;
+}
+
+/// Duration statistics of the commands issued through an [Instrumented] motor, in [Clock] ticks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CommandTiming {
+    pub last: u64,
+    pub min: u64,
+    pub max: u64,
+    pub count: u32,
+}
+
+/// Wraps a motor and measures how long each command takes to apply, which helps to identify slow
+/// buses such as I/O expanders.
+///
+/// # Example
+///
+/// ```ignore
+/// let mut motor = Instrumented::new(&mut motor.a, clock);
+///
+/// motor.forward(50)?;
+/// motor.stop()?;
+///
+/// if let Some(timing) = motor.timing() {
+///     println!("slowest command took {}us", timing.max);
+/// }
+/// ```
+pub struct Instrumented<MOTOR, CLOCK> {
+    motor: MOTOR,
+    clock: CLOCK,
+    timing: Option<CommandTiming>,
+}
+
+impl<MOTOR, CLOCK: Clock> Instrumented<MOTOR, CLOCK> {
+    pub fn new(motor: MOTOR, clock: CLOCK) -> Self {
+        Self {
+            motor,
+            clock,
+            timing: None,
+        }
+    }
+
+    /// Returns the timing statistics, or `None` if no command has been issued yet.
+    pub fn timing(&self) -> Option<CommandTiming> {
+        self.timing
+    }
+
+    /// Discards the statistics collected so far.
+    pub fn reset_timing(&mut self) {
+        self.timing = None;
+    }
+
+    /// Releases the motor and clock.
+    pub fn release(self) -> (MOTOR, CLOCK) {
+        (self.motor, self.clock)
+    }
+
+    fn measure(&mut self, command: impl FnOnce(&mut MOTOR) -> Result<(), MotorDriverError>) -> Result<(), MotorDriverError> {
+        let start = self.clock.now();
+        let result = command(&mut self.motor);
+        let elapsed = self.clock.now().saturating_sub(start);
+
+        self.timing = Some(match self.timing {
+            None => CommandTiming { last: elapsed, min: elapsed, max: elapsed, count: 1 },
+            Some(timing) => CommandTiming {
+                last: elapsed,
+                min: timing.min.min(elapsed),
+                max: timing.max.max(elapsed),
+                count: timing.count.saturating_add(1),
+            },
+        });
+
+        result
+    }
+}
+
+impl<MOTOR: PwmMovement, CLOCK: Clock> PwmMovement for Instrumented<MOTOR, CLOCK> {
+    fn forward(&mut self, percent: u8) -> Result<(), MotorDriverError> {
+        self.measure(|motor| motor.forward(percent))
+    }
+
+    fn reverse(&mut self, percent: u8) -> Result<(), MotorDriverError> {
+        self.measure(|motor| motor.reverse(percent))
+    }
+}
+
+impl<MOTOR: Movement, CLOCK: Clock> Movement for Instrumented<MOTOR, CLOCK> {
+    fn forward(&mut self) -> Result<(), MotorDriverError> {
+        self.measure(|motor| motor.forward())
+    }
+
+    fn reverse(&mut self) -> Result<(), MotorDriverError> {
+        self.measure(|motor| motor.reverse())
+    }
+}
+
+impl<MOTOR: Breaks, CLOCK: Clock> Breaks for Instrumented<MOTOR, CLOCK> {
+    fn coast(&mut self) -> Result<(), MotorDriverError> {
+        self.measure(|motor| motor.coast())
+    }
+
+    fn stop(&mut self) -> Result<(), MotorDriverError> {
+        self.measure(|motor| motor.stop())
+    }
+}
+
+impl<MOTOR: DynMotor, CLOCK: Clock> DynMotor for Instrumented<MOTOR, CLOCK> {
+    fn forward(&mut self, percent: u8) -> Result<(), MotorDriverError> {
+        self.measure(|motor| motor.forward(percent))
+    }
+
+    fn reverse(&mut self, percent: u8) -> Result<(), MotorDriverError> {
+        self.measure(|motor| motor.reverse(percent))
+    }
+
+    fn coast(&mut self) -> Result<(), MotorDriverError> {
+        self.measure(|motor| motor.coast())
+    }
+
+    fn stop(&mut self) -> Result<(), MotorDriverError> {
+        self.measure(|motor| motor.stop())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use embedded_hal_mock::eh1::pin::Mock as Pin;
+    use embedded_hal_mock::eh1::pin::State::{High, Low};
+    use embedded_hal_mock::eh1::pin::Transaction;
+
+    use crate::driver::{Breaks, MotorDriver, MotorDriverError, Movement};
+    use crate::timing::{Clock, CommandTiming, Instrumented};
+
+    /// Advances by the next step on every reading.
+    struct Steps {
+        now: u64,
+        steps: Vec<u64>,
+    }
+
+    impl Clock for Steps {
+        fn now(&mut self) -> u64 {
+            self.now += self.steps.remove(0);
+            self.now
+        }
+    }
+
+    #[test]
+    fn test_records_min_max_and_last_command_duration() -> Result<(), MotorDriverError> {
+        let mut in1 = Pin::new(&[Transaction::set(High), Transaction::set(Low), Transaction::set(High)]);
+        let mut in2 = Pin::new(&[Transaction::set(Low), Transaction::set(Low), Transaction::set(High)]);
+        let mut in3 = Pin::new(&[]);
+        let mut in4 = Pin::new(&[]);
+
+        let mut motor = MotorDriver::new_split(
+            in1.clone(), in2.clone(), in3.clone(), in4.clone(), None::<Pin>, None::<Pin>,
+        );
+
+        let clock = Steps { now: 0, steps: vec![0, 30, 5, 10, 5, 20] };
+        let mut motor = Instrumented::new(&mut motor.a, clock);
+
+        assert_eq!(motor.timing(), None);
+
+        motor.forward()?;
+        motor.coast()?;
+        motor.stop()?;
+
+        assert_eq!(motor.timing(), Some(CommandTiming { last: 20, min: 10, max: 30, count: 3 }));
+
+        motor.reset_timing();
+
+        assert_eq!(motor.timing(), None);
+
+        in1.done();
+        in2.done();
+        in3.done();
+        in4.done();
+
+        Ok(())
+    }
+}